@@ -0,0 +1,56 @@
+use ndarray::{Array2, ArrayView2, ArrayViewMut2, ShapeBuilder};
+
+use {ImmutableSlice, MutableSlice, VecVec};
+
+// Conversions to and from `ndarray` arrays. Grids are indexed as
+// `array[[y, x]]`, so an array has shape `(height, width)`.
+
+impl<T> From<VecVec<T>> for Array2<T> {
+    fn from(vv: VecVec<T>) -> Self {
+        Array2::from_shape_vec((vv.height, vv.width), vv.inner).unwrap()
+    }
+}
+
+/// Reuses the buffer of arrays in standard (row-major) layout and copies others.
+impl<T: Clone> From<Array2<T>> for VecVec<T> {
+    fn from(array: Array2<T>) -> Self {
+        let (height, width) = array.dim();
+        let array = if array.is_standard_layout() {
+            array
+        } else {
+            array.as_standard_layout().into_owned()
+        };
+        let (mut inner, offset) = array.into_raw_vec_and_offset();
+        inner.drain(..offset.unwrap_or(0));
+        inner.truncate(width * height);
+        VecVec {
+            inner,
+            width,
+            height,
+        }
+    }
+}
+
+/// Views the cells of the slice in place, stepping over the rest of each row of
+/// the parent `VecVec`.
+impl<'a, T> From<ImmutableSlice<'a, T>> for ArrayView2<'a, T> {
+    fn from(slice: ImmutableSlice<'a, T>) -> Self {
+        unsafe {
+            let parent = &*slice.ptr;
+            let ptr = parent.inner.as_ptr().wrapping_add(slice.y * parent.width + slice.x);
+            ArrayView2::from_shape_ptr((slice.height, slice.width).strides((parent.width, 1)),
+                                       ptr)
+        }
+    }
+}
+
+impl<'a, T> From<MutableSlice<'a, T>> for ArrayViewMut2<'a, T> {
+    fn from(slice: MutableSlice<'a, T>) -> Self {
+        unsafe {
+            let parent = &mut *(slice.ptr as *mut VecVec<T>);
+            let ptr = parent.inner.as_mut_ptr().wrapping_add(slice.y * parent.width + slice.x);
+            ArrayViewMut2::from_shape_ptr((slice.height, slice.width).strides((parent.width, 1)),
+                                          ptr)
+        }
+    }
+}
@@ -0,0 +1,182 @@
+//! A compact little-endian binary format for grids, written by
+//! `VecVec::write_to` and read by `VecVec::read_from`.
+//!
+//! A file starts with the magic bytes `VVEC`, a version byte, an `Encoding`
+//! byte, the width and height as `u64`s and the size of an element as a `u32`,
+//! followed by the cells in row-major order.
+
+use std::io::{self, Read, Write};
+
+use bytes::invalid_data;
+use {Slice, VecVec};
+
+pub use bytes::Pod;
+
+const MAGIC: &[u8] = b"VVEC";
+const VERSION: u8 = 1;
+
+/// How the cells are stored after the header.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Encoding {
+    /// The bytes of each cell.
+    Raw,
+    /// Each row is replaced by its bytewise wrapping difference from the row
+    /// above, then cells are stored as runs of a `u32` count followed by the
+    /// repeated cell. Suits tile maps with large uniform regions.
+    RunLength,
+}
+
+fn write<T: Pod, M, W: Write>(slice: &Slice<T, M>,
+                              mut writer: W,
+                              encoding: Encoding)
+                              -> io::Result<()> {
+    writer.write_all(MAGIC)?;
+    writer.write_all(&[VERSION, encoding as u8])?;
+    writer.write_all(&(slice.width() as u64).to_le_bytes())?;
+    writer.write_all(&(slice.height() as u64).to_le_bytes())?;
+    writer.write_all(&(T::SIZE as u32).to_le_bytes())?;
+
+    let mut row = Vec::new();
+    let mut previous = vec![0; slice.width() * T::SIZE];
+    let mut run: Option<(Vec<u8>, u32)> = None;
+    for y in 0..slice.height() {
+        row.clear();
+        for x in 0..slice.width() {
+            slice.get(x, y).unwrap().extend_le_bytes(&mut row);
+        }
+        if encoding == Encoding::Raw {
+            writer.write_all(&row)?;
+            continue;
+        }
+        for (byte, previous) in row.iter_mut().zip(&mut previous) {
+            let value = *byte;
+            *byte = byte.wrapping_sub(*previous);
+            *previous = value;
+        }
+        for x in 0..slice.width() {
+            let cell = &row[x * T::SIZE..(x + 1) * T::SIZE];
+            match run {
+                Some((ref bytes, ref mut count)) if bytes[..] == *cell && *count < u32::MAX => {
+                    *count += 1;
+                }
+                _ => {
+                    if let Some((bytes, count)) = run.take() {
+                        writer.write_all(&count.to_le_bytes())?;
+                        writer.write_all(&bytes)?;
+                    }
+                    run = Some((cell.to_vec(), 1));
+                }
+            }
+        }
+    }
+    if let Some((bytes, count)) = run {
+        writer.write_all(&count.to_le_bytes())?;
+        writer.write_all(&bytes)?;
+    }
+    Ok(())
+}
+
+fn read<T: Pod, R: Read>(mut reader: R) -> io::Result<VecVec<T>> {
+    let mut header = [0; 26];
+    reader.read_exact(&mut header)?;
+    if &header[..4] != MAGIC {
+        return Err(invalid_data("not a VecVec file"));
+    }
+    if header[4] != VERSION {
+        return Err(invalid_data(format!("unsupported version {}", header[4])));
+    }
+    let encoding = match header[5] {
+        0 => Encoding::Raw,
+        1 => Encoding::RunLength,
+        encoding => return Err(invalid_data(format!("unknown encoding {}", encoding))),
+    };
+    let mut width = [0; 8];
+    let mut height = [0; 8];
+    let mut size = [0; 4];
+    width.copy_from_slice(&header[6..14]);
+    height.copy_from_slice(&header[14..22]);
+    size.copy_from_slice(&header[22..26]);
+    let (width, height, size) = (u64::from_le_bytes(width),
+                                 u64::from_le_bytes(height),
+                                 u32::from_le_bytes(size) as usize);
+    if size != T::SIZE {
+        return Err(invalid_data(format!("expected elements of {} bytes, found {}", T::SIZE, size)));
+    }
+    let count = width.checked_mul(height)
+        .filter(|&count| count.checked_mul(size as u64).is_some_and(|len| len <= usize::MAX as u64))
+        .ok_or_else(|| invalid_data("grid too large"))? as usize;
+    let (width, height) = (width as usize, height as usize);
+
+    // Buffers grow as data arrives instead of being allocated up front. Raw
+    // cells are read byte for byte, so a truncated raw file with a huge declared
+    // size fails early; a single run can expand to every remaining cell, so
+    // run-length data is only bounded by the declared dimensions.
+    let mut inner = Vec::new();
+    match encoding {
+        Encoding::Raw => {
+            let mut bytes = Vec::new();
+            reader.take((count * size) as u64).read_to_end(&mut bytes)?;
+            if bytes.len() != count * size {
+                return Err(invalid_data("unexpected end of data"));
+            }
+            inner.extend((0..count).map(|i| T::from_le_bytes(&bytes[i * size..(i + 1) * size])));
+        }
+        Encoding::RunLength => {
+            let mut previous = Vec::new();
+            let mut row = Vec::new();
+            let mut cell = vec![0; size];
+            let mut remaining = count;
+            let mut x = 0;
+            while remaining > 0 {
+                let mut run = [0; 4];
+                reader.read_exact(&mut run)?;
+                reader.read_exact(&mut cell)?;
+                let run = u32::from_le_bytes(run) as usize;
+                if run == 0 || run > remaining {
+                    return Err(invalid_data("invalid run length"));
+                }
+                remaining -= run;
+                for _ in 0..run {
+                    row.extend_from_slice(&cell);
+                    x += 1;
+                    if x == width {
+                        previous.resize(row.len(), 0);
+                        for (byte, previous) in row.iter_mut().zip(&mut previous) {
+                            *byte = byte.wrapping_add(*previous);
+                            *previous = *byte;
+                        }
+                        inner.extend((0..width)
+                            .map(|x| T::from_le_bytes(&row[x * size..(x + 1) * size])));
+                        row.clear();
+                        x = 0;
+                    }
+                }
+            }
+        }
+    }
+    Ok(VecVec {
+        inner,
+        width,
+        height,
+    })
+}
+
+impl<T: Pod> VecVec<T> {
+    /// Reads a grid written by `write_to` with either encoding. Fails with
+    /// `InvalidData` if the header is malformed or the elements have a
+    /// different size than `T`. Allocates at most the declared dimensions,
+    /// which a short run-length encoded file can make large.
+    pub fn read_from<R: Read>(reader: R) -> io::Result<Self> {
+        read(reader)
+    }
+
+    pub fn write_to<W: Write>(&self, writer: W, encoding: Encoding) -> io::Result<()> {
+        write(&self.as_slice(), writer, encoding)
+    }
+}
+
+impl<T: Pod, Mutability> Slice<T, Mutability> {
+    pub fn write_to<W: Write>(&self, writer: W, encoding: Encoding) -> io::Result<()> {
+        write(self, writer, encoding)
+    }
+}
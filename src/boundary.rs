@@ -0,0 +1,90 @@
+use {ImmutableSlice, Slice, VecVec};
+
+/// How reads outside the bounds of a grid are resolved. The variants other than
+/// `Skip` match OpenCV's border types.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Boundary<T> {
+    /// Out of range positions are skipped.
+    Skip,
+    /// Out of range positions read the nearest edge cell: `aaa|abcd|ddd`.
+    Clamp,
+    /// Out of range positions wrap around to the other side: `bcd|abcd|abc`.
+    Wrap,
+    /// Out of range positions mirror the grid, repeating the edge cell:
+    /// `cba|abcd|dcb`.
+    Mirror,
+    /// Out of range positions mirror the grid around the edge cell:
+    /// `dcb|abcd|cba`.
+    Reflect101,
+    /// Out of range positions read the given value.
+    Constant(T),
+}
+
+impl<T> Boundary<T> {
+    // Maps a possibly out of range index along an axis of length `len` to an
+    // in range one. Returns `None` if the position should be skipped or read
+    // from `Constant`. Takes an `i128` so that any `usize` position plus an
+    // `isize` offset is represented exactly.
+    pub(crate) fn index(&self, i: i128, len: usize) -> Option<usize> {
+        if len == 0 {
+            return None;
+        }
+        let len = len as i128;
+        if i >= 0 && i < len {
+            return Some(i as usize);
+        }
+        match *self {
+            Boundary::Skip | Boundary::Constant(_) => None,
+            Boundary::Clamp => Some(if i < 0 { 0 } else { len - 1 } as usize),
+            Boundary::Wrap => Some(((i % len + len) % len) as usize),
+            Boundary::Mirror => {
+                let i = ((i % (2 * len)) + 2 * len) % (2 * len);
+                Some(if i < len { i } else { 2 * len - 1 - i } as usize)
+            }
+            Boundary::Reflect101 => {
+                if len == 1 {
+                    return Some(0);
+                }
+                let period = 2 * (len - 1);
+                let i = ((i % period) + period) % period;
+                Some(if i < len { i } else { period - i } as usize)
+            }
+        }
+    }
+}
+
+fn get<'a, T>(slice: ImmutableSlice<'a, T>,
+              x: isize,
+              y: isize,
+              boundary: &'a Boundary<T>)
+              -> Option<&'a T> {
+    match (boundary.index(x as i128, slice.width), boundary.index(y as i128, slice.height)) {
+        (Some(x), Some(y)) => slice.get_ref(x, y),
+        _ => {
+            match *boundary {
+                Boundary::Constant(ref value) => Some(value),
+                _ => None,
+            }
+        }
+    }
+}
+
+impl<T> VecVec<T> {
+    pub fn get_with_boundary<'a>(&'a self,
+                                 x: isize,
+                                 y: isize,
+                                 boundary: &'a Boundary<T>)
+                                 -> Option<&'a T> {
+        get(self.as_slice(), x, y, boundary)
+    }
+}
+
+impl<T, Mutability> Slice<T, Mutability> {
+    pub fn get_with_boundary<'a>(&'a self,
+                                 x: isize,
+                                 y: isize,
+                                 boundary: &'a Boundary<T>)
+                                 -> Option<&'a T> {
+        get(self.as_immutable(), x, y, boundary)
+    }
+}
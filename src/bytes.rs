@@ -0,0 +1,81 @@
+use std::array;
+use std::error::Error;
+use std::io;
+use std::mem;
+
+// Helpers shared by the binary file formats.
+
+pub(crate) fn invalid_data<E: Into<Box<dyn Error + Send + Sync>>>(error: E) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+/// Plain data types with a fixed size byte representation.
+pub trait Pod: Copy {
+    const SIZE: usize;
+
+    fn from_le_bytes(bytes: &[u8]) -> Self;
+    fn from_be_bytes(bytes: &[u8]) -> Self;
+    fn extend_le_bytes(&self, bytes: &mut Vec<u8>);
+}
+
+impl Pod for bool {
+    const SIZE: usize = 1;
+
+    fn from_le_bytes(bytes: &[u8]) -> Self {
+        bytes[0] != 0
+    }
+
+    fn from_be_bytes(bytes: &[u8]) -> Self {
+        bytes[0] != 0
+    }
+
+    fn extend_le_bytes(&self, bytes: &mut Vec<u8>) {
+        bytes.push(*self as u8);
+    }
+}
+
+macro_rules! pod {
+    ($($ty:ty),*) => {
+        $(
+            impl Pod for $ty {
+                const SIZE: usize = mem::size_of::<$ty>();
+
+                fn from_le_bytes(bytes: &[u8]) -> Self {
+                    let mut array = [0; mem::size_of::<$ty>()];
+                    array.copy_from_slice(bytes);
+                    <$ty>::from_le_bytes(array)
+                }
+
+                fn from_be_bytes(bytes: &[u8]) -> Self {
+                    let mut array = [0; mem::size_of::<$ty>()];
+                    array.copy_from_slice(bytes);
+                    <$ty>::from_be_bytes(array)
+                }
+
+                fn extend_le_bytes(&self, bytes: &mut Vec<u8>) {
+                    bytes.extend_from_slice(&self.to_le_bytes());
+                }
+            }
+        )*
+    }
+}
+
+pod!(u8, u16, u32, u64, i8, i16, i32, i64, f32, f64);
+
+impl<T: Pod, const N: usize> Pod for [T; N] {
+    const SIZE: usize = T::SIZE * N;
+
+    fn from_le_bytes(bytes: &[u8]) -> Self {
+        array::from_fn(|i| T::from_le_bytes(&bytes[i * T::SIZE..(i + 1) * T::SIZE]))
+    }
+
+    fn from_be_bytes(bytes: &[u8]) -> Self {
+        array::from_fn(|i| T::from_be_bytes(&bytes[i * T::SIZE..(i + 1) * T::SIZE]))
+    }
+
+    fn extend_le_bytes(&self, bytes: &mut Vec<u8>) {
+        for element in self {
+            element.extend_le_bytes(bytes);
+        }
+    }
+}
@@ -0,0 +1,139 @@
+//! Two-dimensional convolution and correlation.
+//!
+//! All functions produce an output the size of the input with the kernel
+//! anchored at its center (`(width / 2, height / 2)`). Reads outside the input
+//! are resolved with a `Boundary`; cells dropped by `Boundary::Skip` contribute
+//! nothing to the sum.
+
+use {Boundary, Float, Num, Signed, Slice, VecVec};
+
+/// Correlates `input` with `kernel`, i.e. convolves without flipping the kernel.
+pub fn correlate2d<T: Num, M1, M2>(input: &Slice<T, M1>,
+                                   kernel: &Slice<T, M2>,
+                                   boundary: &Boundary<T>)
+                                   -> VecVec<T> {
+    let (ax, ay) = (kernel.width() / 2, kernel.height() / 2);
+    VecVec::from_fn(input.width(), input.height(), |x, y| {
+        let mut sum = T::zero();
+        for ky in 0..kernel.height() {
+            for kx in 0..kernel.width() {
+                let ix = x as isize + kx as isize - ax as isize;
+                let iy = y as isize + ky as isize - ay as isize;
+                if let Some(&value) = input.get_with_boundary(ix, iy, boundary) {
+                    sum = sum + *kernel.get(kx, ky).unwrap() * value;
+                }
+            }
+        }
+        sum
+    })
+}
+
+/// Convolves `input` with `kernel`.
+pub fn convolve2d<T: Num, M1, M2>(input: &Slice<T, M1>,
+                                  kernel: &Slice<T, M2>,
+                                  boundary: &Boundary<T>)
+                                  -> VecVec<T> {
+    let (width, height) = (kernel.width(), kernel.height());
+    let flipped = VecVec::from_fn(width, height, |x, y| {
+        *kernel.get(width - 1 - x, height - 1 - y).unwrap()
+    });
+    correlate2d(input, &flipped.as_slice(), boundary)
+}
+
+/// Correlates `input` with the separable kernel whose columns are scaled by
+/// `kernel_x` and rows by `kernel_y`, in one pass per axis.
+pub fn correlate_separable<T: Num, M>(input: &Slice<T, M>,
+                                      kernel_x: &[T],
+                                      kernel_y: &[T],
+                                      boundary: &Boundary<T>)
+                                      -> VecVec<T> {
+    let (ax, ay) = (kernel_x.len() / 2, kernel_y.len() / 2);
+    let rows = VecVec::from_fn(input.width(), input.height(), |x, y| {
+        let mut sum = T::zero();
+        for (i, &k) in kernel_x.iter().enumerate() {
+            let ix = x as isize + i as isize - ax as isize;
+            if let Some(&value) = input.get_with_boundary(ix, y as isize, boundary) {
+                sum = sum + k * value;
+            }
+        }
+        sum
+    });
+    // A constant row outside the input would have been scaled by the whole of
+    // `kernel_x` in the first pass.
+    let boundary = match *boundary {
+        Boundary::Constant(value) => {
+            Boundary::Constant(kernel_x.iter().fold(T::zero(), |sum, &k| sum + k * value))
+        }
+        ref boundary => *boundary,
+    };
+    VecVec::from_fn(input.width(), input.height(), |x, y| {
+        let mut sum = T::zero();
+        for (i, &k) in kernel_y.iter().enumerate() {
+            let iy = y as isize + i as isize - ay as isize;
+            if let Some(&value) = rows.get_with_boundary(x as isize, iy, &boundary) {
+                sum = sum + k * value;
+            }
+        }
+        sum
+    })
+}
+
+/// Convolves `input` with the separable kernel whose columns are scaled by
+/// `kernel_x` and rows by `kernel_y`, in one pass per axis.
+pub fn convolve_separable<T: Num, M>(input: &Slice<T, M>,
+                                     kernel_x: &[T],
+                                     kernel_y: &[T],
+                                     boundary: &Boundary<T>)
+                                     -> VecVec<T> {
+    let kernel_x = kernel_x.iter().rev().cloned().collect::<Vec<_>>();
+    let kernel_y = kernel_y.iter().rev().cloned().collect::<Vec<_>>();
+    correlate_separable(input, &kernel_x, &kernel_y, boundary)
+}
+
+/// A normalized `width x height` averaging kernel.
+pub fn box_kernel<T: Float>(width: usize, height: usize) -> VecVec<T> {
+    VecVec::new(width, height, T::one() / T::from_f64((width * height) as f64))
+}
+
+/// A normalized one-dimensional Gaussian kernel of length `size`, suitable for
+/// the separable functions.
+pub fn gaussian_kernel_1d<T: Float>(size: usize, sigma: f64) -> Vec<T> {
+    let center = (size / 2) as f64;
+    let weights = (0..size)
+        .map(|i| {
+            let d = i as f64 - center;
+            (-d * d / (2.0 * sigma * sigma)).exp()
+        })
+        .collect::<Vec<_>>();
+    let total = weights.iter().sum::<f64>();
+    weights.into_iter().map(|w| T::from_f64(w / total)).collect()
+}
+
+/// A normalized `size x size` Gaussian kernel.
+pub fn gaussian_kernel<T: Float>(size: usize, sigma: f64) -> VecVec<T> {
+    let weights = gaussian_kernel_1d::<T>(size, sigma);
+    VecVec::from_fn(size, size, |x, y| weights[x] * weights[y])
+}
+
+/// The 3x3 Sobel kernel for the horizontal derivative.
+pub fn sobel_x<T: Signed>() -> VecVec<T> {
+    let (one, two) = (T::one(), T::one() + T::one());
+    kernel3([[-one, T::zero(), one], [-two, T::zero(), two], [-one, T::zero(), one]])
+}
+
+/// The 3x3 Sobel kernel for the vertical derivative.
+pub fn sobel_y<T: Signed>() -> VecVec<T> {
+    let (one, two) = (T::one(), T::one() + T::one());
+    kernel3([[-one, -two, -one], [T::zero(), T::zero(), T::zero()], [one, two, one]])
+}
+
+/// The 3x3 4-connected Laplacian kernel.
+pub fn laplacian<T: Signed>() -> VecVec<T> {
+    let (zero, one) = (T::zero(), T::one());
+    let four = one + one + one + one;
+    kernel3([[zero, one, zero], [one, -four, one], [zero, one, zero]])
+}
+
+fn kernel3<T: Copy>(rows: [[T; 3]; 3]) -> VecVec<T> {
+    VecVec::from_fn(3, 3, |x, y| rows[y][x])
+}
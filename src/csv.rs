@@ -0,0 +1,267 @@
+//! Reading and writing delimited text such as CSV and TSV.
+//!
+//! Fields may be quoted with `"`, in which case they can contain delimiters,
+//! line breaks and `""` for a literal quote, as described in RFC 4180.
+
+use std::error;
+use std::fmt;
+use std::io;
+use std::str::FromStr;
+
+use {Slice, VecVec};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// A row had a different number of fields than the first one. Rows are
+    /// numbered from 0, not counting the header.
+    Ragged {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// A field could not be parsed into the requested type.
+    Parse {
+        row: usize,
+        column: usize,
+        field: String,
+    },
+    /// The input ended inside a quoted field.
+    UnterminatedQuote,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Ragged { row, expected, found } => {
+                write!(f, "row {} has {} fields, expected {}", row, found, expected)
+            }
+            Error::Parse { row, column, ref field } => {
+                write!(f, "invalid field {:?} at row {}, column {}", field, row, column)
+            }
+            Error::UnterminatedQuote => write!(f, "unterminated quoted field"),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+/// Rows read by a `Reader`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Table<T> {
+    /// The first row, if the `Reader` was told to expect a header.
+    pub header: Option<Vec<String>>,
+    pub cells: VecVec<T>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Reader {
+    delimiter: char,
+    has_header: bool,
+    fill: Option<String>,
+}
+
+impl Default for Reader {
+    fn default() -> Self {
+        Reader::new()
+    }
+}
+
+impl Reader {
+    /// A reader for comma-separated text without a header that rejects ragged
+    /// rows.
+    pub fn new() -> Self {
+        Reader {
+            delimiter: ',',
+            has_header: false,
+            fill: None,
+        }
+    }
+
+    /// Like `new`, but for tab-separated text.
+    pub fn tsv() -> Self {
+        Reader::new().delimiter('\t')
+    }
+
+    pub fn delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Whether the first row is a header rather than part of the grid.
+    pub fn has_header(mut self, has_header: bool) -> Self {
+        self.has_header = has_header;
+        self
+    }
+
+    /// Pads rows shorter than the longest one with `fill` instead of rejecting
+    /// them.
+    pub fn pad_with(mut self, fill: &str) -> Self {
+        self.fill = Some(fill.to_string());
+        self
+    }
+
+    pub fn read(&self, text: &str) -> Result<Table<String>, Error> {
+        self.read_parsed(text)
+    }
+
+    /// Reads `text`, parsing every field of the grid with `FromStr`.
+    pub fn read_parsed<T: FromStr>(&self, text: &str) -> Result<Table<T>, Error> {
+        let mut rows = self.records(text)?;
+        let header = if self.has_header && !rows.is_empty() {
+            Some(rows.remove(0))
+        } else {
+            None
+        };
+        let width = match self.fill {
+            Some(_) => rows.iter().map(|row| row.len()).max().unwrap_or(0),
+            None => rows.first().map_or(0, |row| row.len()),
+        };
+        let mut inner = Vec::with_capacity(width * rows.len());
+        for (y, row) in rows.iter().enumerate() {
+            if row.len() != width && self.fill.is_none() {
+                return Err(Error::Ragged {
+                    row: y,
+                    expected: width,
+                    found: row.len(),
+                });
+            }
+            for x in 0..width {
+                let field = row.get(x).or(self.fill.as_ref()).unwrap();
+                inner.push(field.parse().map_err(|_| {
+                    Error::Parse {
+                        row: y,
+                        column: x,
+                        field: field.clone(),
+                    }
+                })?);
+            }
+        }
+        Ok(Table {
+            header,
+            cells: VecVec {
+                inner,
+                width,
+                height: rows.len(),
+            },
+        })
+    }
+
+    fn records(&self, text: &str) -> Result<Vec<Vec<String>>, Error> {
+        let mut records = Vec::new();
+        let mut record = Vec::new();
+        let mut field = String::new();
+        let mut chars = text.chars().peekable();
+        // Whether the current record has any content, so that a trailing line
+        // break does not produce an empty record.
+        let mut started = false;
+        while let Some(c) = chars.next() {
+            started = true;
+            if c == '"' && field.is_empty() {
+                loop {
+                    match chars.next() {
+                        Some('"') if chars.peek() == Some(&'"') => {
+                            chars.next();
+                            field.push('"');
+                        }
+                        Some('"') => break,
+                        Some(c) => field.push(c),
+                        None => return Err(Error::UnterminatedQuote),
+                    }
+                }
+            } else if c == self.delimiter {
+                record.push(field.split_off(0));
+            } else if c == '\n' || c == '\r' {
+                if c == '\r' && chars.peek() == Some(&'\n') {
+                    chars.next();
+                }
+                record.push(field.split_off(0));
+                records.push(record.split_off(0));
+                started = false;
+            } else {
+                field.push(c);
+            }
+        }
+        if started {
+            record.push(field);
+            records.push(record);
+        }
+        Ok(records)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Writer {
+    delimiter: char,
+    header: Option<Vec<String>>,
+}
+
+impl Default for Writer {
+    fn default() -> Self {
+        Writer::new()
+    }
+}
+
+impl Writer {
+    /// A writer for comma-separated text without a header.
+    pub fn new() -> Self {
+        Writer {
+            delimiter: ',',
+            header: None,
+        }
+    }
+
+    /// Like `new`, but for tab-separated text.
+    pub fn tsv() -> Self {
+        Writer::new().delimiter('\t')
+    }
+
+    pub fn delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Writes `header` as the first row.
+    pub fn header<S: ToString>(mut self, header: &[S]) -> Self {
+        self.header = Some(header.iter().map(|field| field.to_string()).collect());
+        self
+    }
+
+    /// Writes the rows of `slice`, each terminated by `\n`, quoting fields that
+    /// contain the delimiter, a quote or a line break.
+    pub fn write<T: fmt::Display, M>(&self, slice: &Slice<T, M>) -> String {
+        let mut out = Vec::new();
+        self.write_to(slice, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    pub fn write_to<T, M, W>(&self, slice: &Slice<T, M>, mut writer: W) -> io::Result<()>
+        where T: fmt::Display,
+              W: io::Write
+    {
+        if let Some(ref header) = self.header {
+            self.write_record(&mut writer, header.iter().map(|field| field.to_string()))?;
+        }
+        for y in 0..slice.height() {
+            let row = (0..slice.width()).map(|x| slice.get(x, y).unwrap().to_string());
+            self.write_record(&mut writer, row)?;
+        }
+        Ok(())
+    }
+
+    fn write_record<W, I>(&self, writer: &mut W, fields: I) -> io::Result<()>
+        where W: io::Write,
+              I: Iterator<Item = String>
+    {
+        for (i, field) in fields.enumerate() {
+            if i > 0 {
+                write!(writer, "{}", self.delimiter)?;
+            }
+            if field.contains(&[self.delimiter, '"', '\n', '\r'][..]) {
+                write!(writer, "\"{}\"", field.replace('"', "\"\""))?;
+            } else {
+                write!(writer, "{}", field)?;
+            }
+        }
+        writeln!(writer)
+    }
+}
@@ -0,0 +1,133 @@
+//! Distance transforms.
+//!
+//! Each function computes, for every cell of a grid, the distance to the
+//! nearest cell for which `is_feature` holds, in time linear in the number of
+//! cells. Cells of a grid without any features are infinitely far away, which
+//! is `f32::INFINITY` for `euclidean` and `u32::MAX` otherwise.
+
+use std::f64;
+
+use {Slice, VecVec};
+
+// Stands in for infinity in the squared Euclidean transform, where the real
+// infinity would produce NaNs.
+const FAR: f64 = 1e20;
+
+/// The Euclidean distance transform, using the algorithm of Felzenszwalb and
+/// Huttenlocher.
+pub fn euclidean<T, M, F>(grid: &Slice<T, M>, mut is_feature: F) -> VecVec<f32>
+    where F: FnMut(&T) -> bool
+{
+    let (width, height) = (grid.width(), grid.height());
+    let mut squared = VecVec::from_fn(width, height, |x, y| {
+        if is_feature(grid.get(x, y).unwrap()) { 0.0 } else { FAR }
+    });
+
+    let n = width.max(height);
+    let (mut d, mut v, mut z) = (vec![0.0; n], vec![0; n], vec![0.0; n + 1]);
+    for x in 0..width {
+        let f = (0..height).map(|y| *squared.get(x, y).unwrap()).collect::<Vec<_>>();
+        transform(&f, &mut d, &mut v, &mut z);
+        for (y, &d) in d[..height].iter().enumerate() {
+            *squared.get_mut(x, y).unwrap() = d;
+        }
+    }
+    for y in 0..height {
+        let f = (0..width).map(|x| *squared.get(x, y).unwrap()).collect::<Vec<_>>();
+        transform(&f, &mut d, &mut v, &mut z);
+        for (x, &d) in d[..width].iter().enumerate() {
+            *squared.get_mut(x, y).unwrap() = d;
+        }
+    }
+
+    VecVec::from_fn(width, height, |x, y| {
+        let d = *squared.get(x, y).unwrap();
+        if d >= FAR { f32::INFINITY } else { d.sqrt() as f32 }
+    })
+}
+
+// The one-dimensional squared distance transform of the sampled function `f`,
+// written to `d`. `v` and `z` are scratch space for the lower envelope of
+// parabolas.
+fn transform(f: &[f64], d: &mut [f64], v: &mut [usize], z: &mut [f64]) {
+    let n = f.len();
+    if n == 0 {
+        return;
+    }
+    let mut k = 0;
+    v[0] = 0;
+    z[0] = f64::NEG_INFINITY;
+    z[1] = f64::INFINITY;
+    for q in 1..n {
+        let intersection = |k: usize| {
+            let (q, p) = (q as f64, v[k] as f64);
+            ((f[q as usize] + q * q) - (f[v[k]] + p * p)) / (2.0 * q - 2.0 * p)
+        };
+        let mut s = intersection(k);
+        while s <= z[k] {
+            k -= 1;
+            s = intersection(k);
+        }
+        k += 1;
+        v[k] = q;
+        z[k] = s;
+        z[k + 1] = f64::INFINITY;
+    }
+    k = 0;
+    for (q, d) in d.iter_mut().enumerate().take(n) {
+        while z[k + 1] < q as f64 {
+            k += 1;
+        }
+        let offset = q as f64 - v[k] as f64;
+        *d = offset * offset + f[v[k]];
+    }
+}
+
+/// The Manhattan (4-connected) distance transform.
+pub fn manhattan<T, M, F>(grid: &Slice<T, M>, is_feature: F) -> VecVec<u32>
+    where F: FnMut(&T) -> bool
+{
+    chamfer(grid, is_feature, &[(-1, 0), (0, -1)])
+}
+
+/// The Chebyshev (8-connected) distance transform.
+pub fn chebyshev<T, M, F>(grid: &Slice<T, M>, is_feature: F) -> VecVec<u32>
+    where F: FnMut(&T) -> bool
+{
+    chamfer(grid, is_feature, &[(-1, 0), (-1, -1), (0, -1), (1, -1)])
+}
+
+// A two-pass chamfer transform with unit step costs. `before` lists the
+// offsets of the neighbors visited before a cell in row-major order; the
+// backward pass uses their mirror images.
+fn chamfer<T, M, F>(grid: &Slice<T, M>, mut is_feature: F, before: &[(isize, isize)]) -> VecVec<u32>
+    where F: FnMut(&T) -> bool
+{
+    let (width, height) = (grid.width(), grid.height());
+    let mut distances = VecVec::from_fn(width, height, |x, y| {
+        if is_feature(grid.get(x, y).unwrap()) { 0 } else { u32::MAX }
+    });
+    let relax = |distances: &mut VecVec<u32>, x: usize, y: usize, sign: isize| {
+        let mut best = *distances.get(x, y).unwrap();
+        for &(dx, dy) in before {
+            let (nx, ny) = (x as isize + sign * dx, y as isize + sign * dy);
+            if nx >= 0 && ny >= 0 {
+                if let Some(&d) = distances.get(nx as usize, ny as usize) {
+                    best = best.min(d.saturating_add(1));
+                }
+            }
+        }
+        *distances.get_mut(x, y).unwrap() = best;
+    };
+    for y in 0..height {
+        for x in 0..width {
+            relax(&mut distances, x, y, 1);
+        }
+    }
+    for y in (0..height).rev() {
+        for x in (0..width).rev() {
+            relax(&mut distances, x, y, -1);
+        }
+    }
+    distances
+}
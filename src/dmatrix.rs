@@ -0,0 +1,30 @@
+use nalgebra::{DMatrix, DMatrixView, Dyn, Scalar};
+
+use {ImmutableSlice, VecVec};
+
+// Conversions to and from `nalgebra` matrices. Row `y` of a grid is row `y` of
+// the matrix, so a matrix has `height` rows and `width` columns.
+
+/// Reorders the cells into nalgebra's column-major layout.
+impl<T: Scalar> From<VecVec<T>> for DMatrix<T> {
+    fn from(vv: VecVec<T>) -> Self {
+        DMatrix::from_row_iterator(vv.height, vv.width, vv.inner)
+    }
+}
+
+impl<T: Scalar> From<DMatrix<T>> for VecVec<T> {
+    fn from(matrix: DMatrix<T>) -> Self {
+        VecVec::from_fn(matrix.ncols(), matrix.nrows(), |x, y| matrix[(y, x)].clone())
+    }
+}
+
+/// Views the cells of the slice in place, with a row stride of the width of
+/// the parent `VecVec`.
+impl<'a, T: Scalar> From<ImmutableSlice<'a, T>> for DMatrixView<'a, T, Dyn, Dyn> {
+    fn from(slice: ImmutableSlice<'a, T>) -> Self {
+        let parent = unsafe { &*slice.ptr };
+        let start = slice.y * parent.width + slice.x;
+        let data = parent.inner.get(start..).unwrap_or(&[]);
+        DMatrixView::from_slice_with_strides(data, slice.height, slice.width, parent.width, 1)
+    }
+}
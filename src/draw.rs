@@ -0,0 +1,360 @@
+//! Rasterization of lines and shapes.
+//!
+//! Shapes are positioned with signed coordinates relative to the slice drawn
+//! into and clipped to its bounds before they are rasterized, so drawing only
+//! visits the cells of a shape that lie within the slice. The `*_points`
+//! functions lazily yield the rasterized coordinates of a shape without
+//! clipping, for callers that want to write cells themselves, leaving out only
+//! points beyond the range of `isize`.
+
+use std::iter;
+use std::ops::Range;
+
+use MutableSlice;
+
+pub type Point = (isize, isize);
+
+// The coordinates representable in a `Point`. Shapes are computed with `i128`
+// coordinates, which cannot overflow for any `isize` position and `usize` size.
+const POINTS: Range<i128> = isize::MIN as i128..isize::MAX as i128 + 1;
+
+/// Iterator over the points of a line between two points inclusive, as
+/// rasterized by Bresenham's algorithm.
+#[derive(Clone, Debug)]
+pub struct Line {
+    from: (i128, i128),
+    // Whether x is the axis advancing on every step; the other one advances
+    // `minor` times over the `major` steps.
+    x_major: bool,
+    major: u128,
+    minor: u128,
+    sx: i128,
+    sy: i128,
+    steps: Range<u128>,
+}
+
+impl Line {
+    // The point after `step` steps. Computed directly rather than by tracking
+    // an error term, so that clipping can jump to any step. Rounding half up
+    // matches the choices of the incremental algorithm.
+    fn at(&self, step: u128) -> (i128, i128) {
+        // Cannot overflow, as both factors are at most `usize::MAX`.
+        let product = step * self.minor;
+        let minor = match product.checked_div(self.major) {
+            Some(quotient) => {
+                let remainder = product % self.major;
+                quotient + (remainder >= self.major - remainder) as u128
+            }
+            // A single point.
+            None => 0,
+        };
+        let (dx, dy) = if self.x_major { (step, minor) } else { (minor, step) };
+        (self.from.0 + self.sx * dx as i128, self.from.1 + self.sy * dy as i128)
+    }
+
+    // Restricts the line to its points within `columns` and `rows`.
+    fn clip(mut self, columns: &Range<i128>, rows: &Range<i128>) -> Line {
+        let xs = self.within(|(x, _)| x, self.sx, columns);
+        let ys = self.within(|(_, y)| y, self.sy, rows);
+        let start = xs.start.max(ys.start);
+        self.steps = start..xs.end.min(ys.end).max(start);
+        self
+    }
+
+    // The steps at which the coordinate picked by `f`, which only ever moves in
+    // the direction of `sign`, lies within `range`.
+    fn within<F>(&self, f: F, sign: i128, range: &Range<i128>) -> Range<u128>
+        where F: Fn((i128, i128)) -> i128
+    {
+        let steps = self.steps.clone();
+        if sign > 0 {
+            first(steps.clone(), |step| f(self.at(step)) >= range.start)..
+            first(steps, |step| f(self.at(step)) >= range.end)
+        } else {
+            first(steps.clone(), |step| f(self.at(step)) < range.end)..
+            first(steps, |step| f(self.at(step)) < range.start)
+        }
+    }
+}
+
+impl Iterator for Line {
+    type Item = Point;
+
+    fn next(&mut self) -> Option<Point> {
+        // Every point lies between the two ends, so fits in a `Point`.
+        self.steps.next().map(|step| {
+            let (x, y) = self.at(step);
+            (x as isize, y as isize)
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.steps.size_hint()
+    }
+}
+
+pub fn line_points(from: Point, to: Point) -> Line {
+    let dx = (to.0 as i128 - from.0 as i128).unsigned_abs();
+    let dy = (to.1 as i128 - from.1 as i128).unsigned_abs();
+    Line {
+        from: (from.0 as i128, from.1 as i128),
+        x_major: dx >= dy,
+        major: dx.max(dy),
+        minor: dx.min(dy),
+        sx: if from.0 < to.0 { 1 } else { -1 },
+        sy: if from.1 < to.1 { 1 } else { -1 },
+        steps: 0..dx.max(dy) + 1,
+    }
+}
+
+// The first value in `range` for which `f` holds, or the end of `range` if
+// there is none. `f` must hold for every value after one for which it holds.
+fn first<F: Fn(u128) -> bool>(range: Range<u128>, f: F) -> u128 {
+    let (mut low, mut high) = (range.start, range.end);
+    while low < high {
+        let middle = low + (high - low) / 2;
+        if f(middle) {
+            high = middle;
+        } else {
+            low = middle + 1;
+        }
+    }
+    low
+}
+
+/// The outline of the `width x height` rectangle with its top-left corner at
+/// `(x, y)`.
+pub fn rect_points(x: isize,
+                   y: isize,
+                   width: usize,
+                   height: usize)
+                   -> impl Iterator<Item = Point> {
+    edges(x, y, width, height)
+        .into_iter()
+        .flat_map(|(xs, ys)| cells(clip(xs, &POINTS), clip(ys, &POINTS)))
+}
+
+pub fn fill_rect_points(x: isize,
+                        y: isize,
+                        width: usize,
+                        height: usize)
+                        -> impl Iterator<Item = Point> {
+    cells(clip(span(x, width), &POINTS), clip(span(y, height), &POINTS))
+}
+
+// The top, left, right and bottom edges of a rectangle outline as `(xs, ys)`
+// ranges, leaving out those that are empty or overlap an earlier edge.
+fn edges(x: isize, y: isize, width: usize, height: usize) -> Vec<(Range<i128>, Range<i128>)> {
+    let (xs, ys) = (span(x, width), span(y, height));
+    let mut edges = Vec::new();
+    if xs.is_empty() || ys.is_empty() {
+        return edges;
+    }
+    edges.push((xs.clone(), ys.start..ys.start + 1));
+    if height > 1 {
+        let inner = ys.start + 1..ys.end - 1;
+        edges.push((xs.start..xs.start + 1, inner.clone()));
+        if width > 1 {
+            edges.push((xs.end - 1..xs.end, inner));
+        }
+        edges.push((xs, ys.end - 1..ys.end));
+    }
+    edges
+}
+
+fn span(start: isize, len: usize) -> Range<i128> {
+    start as i128..start as i128 + len as i128
+}
+
+// The part of `range` that lies within `bounds`.
+fn clip(range: Range<i128>, bounds: &Range<i128>) -> Range<i128> {
+    let start = range.start.max(bounds.start);
+    start..range.end.min(bounds.end).max(start)
+}
+
+// The points of the rectangle spanning `xs` and `ys`, which must lie within
+// `POINTS`, in row-major order.
+fn cells(xs: Range<i128>, ys: Range<i128>) -> impl Iterator<Item = Point> {
+    ys.flat_map(move |y| xs.clone().map(move |x| (x as isize, y as isize)))
+}
+
+// The midpoint circle of a radius. Its first octant holds a point `(x(y), y)`
+// for each row `y` up to `last`.
+#[derive(Clone, Copy, Debug)]
+struct Circle {
+    radius: u128,
+    last: u128,
+}
+
+impl Circle {
+    fn new(radius: usize) -> Self {
+        let radius = radius as u128;
+        let circle = Circle { radius, last: 0 };
+        let last = first(0..radius + 1, |y| circle.x(y) < y) - 1;
+        Circle { radius, last }
+    }
+
+    // `sqrt(radius² - y²)` rounded half up, which is where the midpoint
+    // algorithm places the first octant point of row `y`. Cannot overflow, as
+    // the radius is at most `usize::MAX`.
+    fn x(&self, y: u128) -> u128 {
+        let squared = self.radius * self.radius - y * y;
+        let root = squared.isqrt();
+        if squared - root * root > root { root + 1 } else { root }
+    }
+
+    // The smallest and largest distance from the centre of the outline points
+    // in the row `dy` away from the centre, which lie in between. The row holds
+    // the point of the first octant in that row and the mirrored points of the
+    // first octant in that column.
+    fn row(&self, dy: u128) -> (u128, u128) {
+        if dy < self.last {
+            let x = self.x(dy);
+            return (x, x);
+        }
+        let ys = first(0..self.last + 1, |y| self.x(y) <= dy)..
+                 first(0..self.last + 1, |y| self.x(y) < dy);
+        if dy > self.last {
+            (ys.start, ys.end - 1)
+        } else if ys.is_empty() {
+            let x = self.x(dy);
+            (x, x)
+        } else {
+            let x = self.x(dy);
+            (ys.start.min(x), (ys.end - 1).max(x))
+        }
+    }
+}
+
+// The cells of the circle of `radius` around `center` within `columns` and
+// `rows`, as `(y, xs)` runs in row-major order. Only rows within `rows` are
+// computed. Leaves out the interior if `outline` is set.
+fn runs(center: Point,
+        radius: usize,
+        columns: Range<i128>,
+        rows: Range<i128>,
+        outline: bool)
+        -> impl Iterator<Item = (i128, Range<i128>)> {
+    let circle = Circle::new(radius);
+    let (cx, cy, radius) = (center.0 as i128, center.1 as i128, radius as i128);
+    clip(cy - radius..cy + radius + 1, &rows).flat_map(move |y| {
+        let (inner, outer) = circle.row((y - cy).unsigned_abs());
+        let (inner, outer) = (inner as i128, outer as i128);
+        let (left, right) = if outline && inner > 0 {
+            (cx - outer..cx - inner + 1, cx + inner..cx + outer + 1)
+        } else {
+            (cx - outer..cx + outer + 1, 0..0)
+        };
+        let columns = columns.clone();
+        iter::once(left)
+            .chain(iter::once(right))
+            .map(move |xs| clip(xs, &columns))
+            .filter(|xs| !xs.is_empty())
+            .map(move |xs| (y, xs))
+    })
+}
+
+/// The outline of the circle of `radius` around `center`, using the midpoint
+/// algorithm, in row-major order.
+pub fn circle_points(center: Point, radius: usize) -> impl Iterator<Item = Point> {
+    runs(center, radius, POINTS, POINTS, true).flat_map(|(y, xs)| cells(xs, y..y + 1))
+}
+
+/// The circle of `radius` around `center` and its interior, matching the
+/// outline of `circle_points`, in row-major order.
+pub fn fill_circle_points(center: Point, radius: usize) -> impl Iterator<Item = Point> {
+    runs(center, radius, POINTS, POINTS, false).flat_map(|(y, xs)| cells(xs, y..y + 1))
+}
+
+// The edges of the closed polygon through `vertices`, each leaving out its
+// first point, which ends the previous edge, unless a single vertex is its own
+// edge.
+fn polygon_edges(vertices: &[Point]) -> impl Iterator<Item = Line> + '_ {
+    let skip = if vertices.len() == 1 { 0 } else { 1 };
+    (0..vertices.len()).map(move |i| {
+        let mut line = line_points(vertices[i], vertices[(i + 1) % vertices.len()]);
+        line.steps.start = skip;
+        line
+    })
+}
+
+/// The outline of the closed polygon through `vertices`.
+pub fn polygon_points(vertices: &[Point]) -> impl Iterator<Item = Point> + '_ {
+    polygon_edges(vertices).flatten()
+}
+
+/// Sets every cell at one of `points` that lies within `slice` to `value`.
+pub fn plot<T, I>(slice: &mut MutableSlice<T>, points: I, value: T)
+    where T: Clone,
+          I: IntoIterator<Item = Point>
+{
+    for (x, y) in points {
+        if x >= 0 && y >= 0 {
+            if let Some(cell) = slice.get_mut(x as usize, y as usize) {
+                *cell = value.clone();
+            }
+        }
+    }
+}
+
+// The columns and rows of `slice`.
+fn bounds<T>(slice: &MutableSlice<T>) -> (Range<i128>, Range<i128>) {
+    (0..slice.width() as i128, 0..slice.height() as i128)
+}
+
+// Sets the cells spanning `xs` and `ys`, which must lie within `slice`.
+fn fill<T: Clone>(slice: &mut MutableSlice<T>, xs: Range<i128>, ys: Range<i128>, value: T) {
+    for y in ys {
+        for x in xs.clone() {
+            *slice.get_mut(x as usize, y as usize).unwrap() = value.clone();
+        }
+    }
+}
+
+pub fn line<T: Clone>(slice: &mut MutableSlice<T>, from: Point, to: Point, value: T) {
+    let (columns, rows) = bounds(slice);
+    plot(slice, line_points(from, to).clip(&columns, &rows), value)
+}
+
+pub fn rect<T: Clone>(slice: &mut MutableSlice<T>,
+                      x: isize,
+                      y: isize,
+                      width: usize,
+                      height: usize,
+                      value: T) {
+    let (columns, rows) = bounds(slice);
+    for (xs, ys) in edges(x, y, width, height) {
+        fill(slice, clip(xs, &columns), clip(ys, &rows), value.clone());
+    }
+}
+
+pub fn fill_rect<T: Clone>(slice: &mut MutableSlice<T>,
+                           x: isize,
+                           y: isize,
+                           width: usize,
+                           height: usize,
+                           value: T) {
+    let (columns, rows) = bounds(slice);
+    fill(slice, clip(span(x, width), &columns), clip(span(y, height), &rows), value)
+}
+
+pub fn circle<T: Clone>(slice: &mut MutableSlice<T>, center: Point, radius: usize, value: T) {
+    let (columns, rows) = bounds(slice);
+    for (y, xs) in runs(center, radius, columns, rows, true) {
+        fill(slice, xs, y..y + 1, value.clone());
+    }
+}
+
+pub fn fill_circle<T: Clone>(slice: &mut MutableSlice<T>, center: Point, radius: usize, value: T) {
+    let (columns, rows) = bounds(slice);
+    for (y, xs) in runs(center, radius, columns, rows, false) {
+        fill(slice, xs, y..y + 1, value.clone());
+    }
+}
+
+pub fn polygon<T: Clone>(slice: &mut MutableSlice<T>, vertices: &[Point], value: T) {
+    let (columns, rows) = bounds(slice);
+    for edge in polygon_edges(vertices) {
+        plot(slice, edge.clip(&columns, &rows), value.clone());
+    }
+}
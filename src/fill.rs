@@ -0,0 +1,107 @@
+use neighbors::adjacent;
+use {Connectivity, Mutable, Rect, Slice, VecVec};
+
+/// Statistics about one connected component found by `label_components`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Component {
+    /// The number of cells in the component.
+    pub area: usize,
+    /// The smallest rectangle containing every cell of the component.
+    pub bounds: Rect,
+}
+
+impl<T> VecVec<T> {
+    pub fn flood_fill(&mut self, x: usize, y: usize, value: T, connectivity: Connectivity) -> usize
+        where T: Clone + PartialEq
+    {
+        self.as_mut_slice().flood_fill(x, y, value, connectivity)
+    }
+
+    pub fn label_components<F>(&self,
+                               connectivity: Connectivity,
+                               same: F)
+                               -> (VecVec<u32>, Vec<Component>)
+        where F: FnMut(&T, &T) -> bool
+    {
+        self.as_slice().label_components(connectivity, same)
+    }
+}
+
+impl<'a, T: 'a> Slice<T, Mutable<'a, T>> {
+    /// Sets every cell connected to `(x, y)` through cells equal to it to
+    /// `value`, without leaving this slice. Returns the number of cells set,
+    /// which is 0 if `(x, y)` is out of range.
+    pub fn flood_fill(&mut self, x: usize, y: usize, value: T, connectivity: Connectivity) -> usize
+        where T: Clone + PartialEq
+    {
+        let target = match self.get(x, y) {
+            Some(target) => target.clone(),
+            None => return 0,
+        };
+        let mut visited = VecVec::new(self.width, self.height, false);
+        let mut stack = vec![(x, y)];
+        *visited.get_mut(x, y).unwrap() = true;
+        let mut count = 0;
+        while let Some((x, y)) = stack.pop() {
+            *self.get_mut(x, y).unwrap() = value.clone();
+            count += 1;
+            for (nx, ny) in adjacent(x, y, self.width, self.height, connectivity) {
+                if !visited.get(nx, ny).unwrap() && *self.get(nx, ny).unwrap() == target {
+                    *visited.get_mut(nx, ny).unwrap() = true;
+                    stack.push((nx, ny));
+                }
+            }
+        }
+        count
+    }
+}
+
+impl<T, Mutability> Slice<T, Mutability> {
+    /// Partitions the slice into components of cells linked by adjacent pairs
+    /// for which `same` holds. Returns a grid of component labels, numbered
+    /// from 0 in row-major order of their first cell, and each component's
+    /// statistics indexed by label.
+    pub fn label_components<F>(&self,
+                               connectivity: Connectivity,
+                               mut same: F)
+                               -> (VecVec<u32>, Vec<Component>)
+        where F: FnMut(&T, &T) -> bool
+    {
+        const UNLABELED: u32 = u32::MAX;
+        let mut labels = VecVec::new(self.width, self.height, UNLABELED);
+        let mut components = Vec::new();
+        let mut stack = Vec::new();
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if *labels.get(x, y).unwrap() != UNLABELED {
+                    continue;
+                }
+                let label = components.len() as u32;
+                let (mut x0, mut y0, mut x1, mut y1) = (x, y, x, y);
+                let mut area = 0;
+                *labels.get_mut(x, y).unwrap() = label;
+                stack.push((x, y));
+                while let Some((x, y)) = stack.pop() {
+                    area += 1;
+                    x0 = x0.min(x);
+                    y0 = y0.min(y);
+                    x1 = x1.max(x);
+                    y1 = y1.max(y);
+                    let value = self.get(x, y).unwrap();
+                    for (nx, ny) in adjacent(x, y, self.width, self.height, connectivity) {
+                        if *labels.get(nx, ny).unwrap() == UNLABELED &&
+                           same(value, self.get(nx, ny).unwrap()) {
+                            *labels.get_mut(nx, ny).unwrap() = label;
+                            stack.push((nx, ny));
+                        }
+                    }
+                }
+                components.push(Component {
+                    area,
+                    bounds: Rect::new(x0, y0, x1 - x0 + 1, y1 - y0 + 1),
+                });
+            }
+        }
+        (labels, components)
+    }
+}
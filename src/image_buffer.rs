@@ -0,0 +1,68 @@
+use std::convert::TryFrom;
+use std::error;
+use std::fmt;
+
+use image::{GenericImageView, ImageBuffer, Pixel};
+
+use {Slice, VecVec};
+
+// Conversions between grids of pixels and `image` buffers, and slices as
+// read-only images. Dimensions are `u32` in `image`.
+
+/// The error converting a grid into an image when its width or height exceeds
+/// `u32::MAX`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ImageTooLarge {
+    pub width: usize,
+    pub height: usize,
+}
+
+impl fmt::Display for ImageTooLarge {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "grid of {}x{} cells too large for an image", self.width, self.height)
+    }
+}
+
+impl error::Error for ImageTooLarge {}
+
+fn dimensions(width: usize, height: usize) -> Result<(u32, u32), ImageTooLarge> {
+    if width > u32::MAX as usize || height > u32::MAX as usize {
+        return Err(ImageTooLarge { width, height });
+    }
+    Ok((width as u32, height as u32))
+}
+
+impl<P: Pixel> TryFrom<VecVec<P>> for ImageBuffer<P, Vec<P::Subpixel>> {
+    type Error = ImageTooLarge;
+
+    fn try_from(vv: VecVec<P>) -> Result<Self, ImageTooLarge> {
+        let (width, height) = dimensions(vv.width, vv.height)?;
+        let data = vv.inner.iter().flat_map(|pixel| pixel.channels().iter().cloned()).collect();
+        Ok(ImageBuffer::from_raw(width, height, data).unwrap())
+    }
+}
+
+impl<P: Pixel> From<ImageBuffer<P, Vec<P::Subpixel>>> for VecVec<P> {
+    fn from(image: ImageBuffer<P, Vec<P::Subpixel>>) -> Self {
+        VecVec {
+            inner: image.pixels().cloned().collect(),
+            width: image.width() as usize,
+            height: image.height() as usize,
+        }
+    }
+}
+
+/// Lets a slice be passed to `image::imageops` and anything else taking a
+/// `GenericImageView`, like `imageops::replace` to copy it into an image.
+/// `dimensions` panics if the width or height exceeds `u32::MAX`.
+impl<P: Pixel, Mutability> GenericImageView for Slice<P, Mutability> {
+    type Pixel = P;
+
+    fn dimensions(&self) -> (u32, u32) {
+        dimensions(self.width, self.height).expect("grid too large for an image")
+    }
+
+    fn get_pixel(&self, x: u32, y: u32) -> P {
+        *self.get(x as usize, y as usize).expect("pixel out of bounds")
+    }
+}
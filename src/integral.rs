@@ -0,0 +1,100 @@
+use {Num, Rect, Slice, VecVec};
+
+/// A summed-area table answering sums over any rectangle in constant time.
+///
+/// Sums are accumulated in `T`, which may be wider than the element type of
+/// the source so that large images do not overflow, e.g. an `Integral<u64>`
+/// of a `VecVec<u8>`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Integral<T> {
+    // One row and column larger than the source, with a leading row and column
+    // of zeros, so that `table[x, y]` is the sum of all cells above and to the
+    // left of `(x, y)`.
+    table: VecVec<T>,
+}
+
+impl<T: Num> Integral<T> {
+    pub fn new<E, Mutability>(slice: &Slice<E, Mutability>) -> Self
+        where E: Copy + Into<T>
+    {
+        let mut table = VecVec::new(slice.width() + 1, slice.height() + 1, T::zero());
+        for y in 0..slice.height() {
+            let mut row = T::zero();
+            for x in 0..slice.width() {
+                row = row + (*slice.get(x, y).unwrap()).into();
+                let above = *table.get(x + 1, y).unwrap();
+                *table.get_mut(x + 1, y + 1).unwrap() = above + row;
+            }
+        }
+        Integral { table }
+    }
+
+    pub fn width(&self) -> usize {
+        self.table.width() - 1
+    }
+
+    pub fn height(&self) -> usize {
+        self.table.height() - 1
+    }
+
+    /// The sum of the cells in `rect`, or `None` if it extends out of range.
+    pub fn sum(&self, rect: Rect) -> Option<T> {
+        let (x0, y0) = (rect.x, rect.y);
+        let x1 = rect.x.checked_add(rect.width)?;
+        let y1 = rect.y.checked_add(rect.height)?;
+        if x1 > self.width() || y1 > self.height() {
+            return None;
+        }
+        let at = |x, y| *self.table.get(x, y).unwrap();
+        // Both differences are sums of cells, so unsigned types never underflow.
+        Some((at(x1, y1) - at(x1, y0)) - (at(x0, y1) - at(x0, y0)))
+    }
+
+    /// Updates the table after `delta` was added to the cell at `(x, y)`.
+    /// Takes time proportional to the number of cells below and to the right
+    /// of it. Panics if `(x, y)` is out of range.
+    pub fn add(&mut self, x: usize, y: usize, delta: T) {
+        self.apply(x, y, |value| value + delta)
+    }
+
+    /// Updates the table after the cell at `(x, y)` changed from `old` to
+    /// `new`. Panics if `(x, y)` is out of range.
+    pub fn update(&mut self, x: usize, y: usize, old: T, new: T) {
+        // Subtracting the decrease keeps unsigned types from underflowing.
+        if new < old {
+            self.apply(x, y, |value| value - (old - new))
+        } else {
+            self.add(x, y, new - old)
+        }
+    }
+
+    fn apply<F>(&mut self, x: usize, y: usize, f: F)
+        where F: Fn(T) -> T
+    {
+        assert!(x < self.width() && y < self.height(), "position out of range");
+        for ty in y + 1..self.table.height() {
+            for tx in x + 1..self.table.width() {
+                let value = self.table.get_mut(tx, ty).unwrap();
+                *value = f(*value);
+            }
+        }
+    }
+}
+
+impl<T: Copy> VecVec<T> {
+    /// The summed-area table of this grid, accumulating sums in `S`.
+    pub fn integral<S: Num>(&self) -> Integral<S>
+        where T: Into<S>
+    {
+        Integral::new(&self.as_slice())
+    }
+}
+
+impl<T: Copy, Mutability> Slice<T, Mutability> {
+    /// The summed-area table of this slice, accumulating sums in `S`.
+    pub fn integral<S: Num>(&self) -> Integral<S>
+        where T: Into<S>
+    {
+        Integral::new(self)
+    }
+}
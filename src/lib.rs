@@ -1,5 +1,63 @@
+#[cfg(feature = "image")]
+extern crate image;
+#[cfg(feature = "nalgebra")]
+extern crate nalgebra;
+#[cfg(feature = "ndarray")]
+extern crate ndarray;
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde;
+
 use std::{fmt, marker};
 
+pub use boundary::Boundary;
+pub use fill::Component;
+#[cfg(feature = "image")]
+pub use image_buffer::ImageTooLarge;
+pub use integral::Integral;
+pub use neighbors::{Connectivity, Neighbors, Stencil};
+pub use num::{Float, Num, Signed};
+pub use rect::Rect;
+pub use resample::Interpolate;
+pub use text::Text;
+pub use windows::Windows;
+
+pub mod binary;
+pub mod conv;
+pub mod csv;
+pub mod distance;
+pub mod draw;
+pub mod netpbm;
+pub mod npy;
+pub mod path;
+#[cfg(feature = "serde")]
+pub mod serde_rows;
+
+#[cfg(feature = "ndarray")]
+mod array;
+mod boundary;
+mod bytes;
+#[cfg(feature = "nalgebra")]
+mod dmatrix;
+mod fill;
+#[cfg(feature = "image")]
+mod image_buffer;
+mod integral;
+mod matrix;
+mod neighbors;
+mod num;
+mod ops;
+mod rect;
+mod reduce;
+mod resample;
+mod search;
+#[cfg(feature = "serde")]
+mod serialize;
+mod stats;
+mod table;
+mod text;
+mod windows;
+
 #[derive(Clone, PartialEq, Eq)]
 pub struct VecVec<T> {
     inner: Vec<T>,
@@ -13,8 +71,24 @@ impl<T> VecVec<T> {
     {
         VecVec {
             inner: vec![value; width * height],
-            width: width,
-            height: height,
+            width,
+            height,
+        }
+    }
+
+    pub fn from_fn<F>(width: usize, height: usize, mut f: F) -> Self
+        where F: FnMut(usize, usize) -> T
+    {
+        let mut inner = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                inner.push(f(x, y));
+            }
+        }
+        VecVec {
+            inner,
+            width,
+            height,
         }
     }
 
@@ -47,7 +121,7 @@ impl<T> VecVec<T> {
                  y: usize,
                  width: usize,
                  height: usize)
-                 -> Option<ImmutableSlice<T>> {
+                 -> Option<ImmutableSlice<'_, T>> {
         self.as_slice().slice(x, y, width, height)
     }
 
@@ -56,27 +130,35 @@ impl<T> VecVec<T> {
                      y: usize,
                      width: usize,
                      height: usize)
-                     -> Option<MutableSlice<T>> {
+                     -> Option<MutableSlice<'_, T>> {
         self.as_mut_slice().slice_mut(x, y, width, height)
     }
 
-    pub fn hsplit_at(&self, y: usize) -> Option<(ImmutableSlice<T>, ImmutableSlice<T>)> {
+    pub fn row(&self, y: usize) -> Option<ImmutableSlice<'_, T>> {
+        self.as_slice().row_ref(y)
+    }
+
+    pub fn column(&self, x: usize) -> Option<ImmutableSlice<'_, T>> {
+        self.as_slice().column_ref(x)
+    }
+
+    pub fn hsplit_at(&self, y: usize) -> Option<(ImmutableSlice<'_, T>, ImmutableSlice<'_, T>)> {
         self.as_slice().hsplit_at(y)
     }
 
-    pub fn vsplit_at(&self, x: usize) -> Option<(ImmutableSlice<T>, ImmutableSlice<T>)> {
+    pub fn vsplit_at(&self, x: usize) -> Option<(ImmutableSlice<'_, T>, ImmutableSlice<'_, T>)> {
         self.as_slice().vsplit_at(x)
     }
 
-    pub fn hsplit_at_mut(&mut self, y: usize) -> Option<(MutableSlice<T>, MutableSlice<T>)> {
+    pub fn hsplit_at_mut(&mut self, y: usize) -> Option<(MutableSlice<'_, T>, MutableSlice<'_, T>)> {
         self.as_mut_slice().hsplit_at_mut(y)
     }
 
-    pub fn vsplit_at_mut(&mut self, x: usize) -> Option<(MutableSlice<T>, MutableSlice<T>)> {
+    pub fn vsplit_at_mut(&mut self, x: usize) -> Option<(MutableSlice<'_, T>, MutableSlice<'_, T>)> {
         self.as_mut_slice().vsplit_at_mut(x)
     }
 
-    pub fn as_slice(&self) -> ImmutableSlice<T> {
+    pub fn as_slice(&self) -> ImmutableSlice<'_, T> {
         Slice {
             ptr: self as *const _,
             x: 0,
@@ -87,7 +169,7 @@ impl<T> VecVec<T> {
         }
     }
 
-    pub fn as_mut_slice(&self) -> MutableSlice<T> {
+    pub fn as_mut_slice(&self) -> MutableSlice<'_, T> {
         Slice {
             ptr: self as *const _,
             x: 0,
@@ -151,6 +233,17 @@ impl<'a, T, Mutability> Slice<T, Mutability> {
         }
     }
 
+    fn as_immutable(&self) -> ImmutableSlice<'_, T> {
+        Slice {
+            ptr: self.ptr,
+            x: self.x,
+            y: self.y,
+            width: self.width,
+            height: self.height,
+            _mutability: Immutable { marker: marker::PhantomData },
+        }
+    }
+
     pub fn slice(&self,
                  x: usize,
                  y: usize,
@@ -162,8 +255,8 @@ impl<'a, T, Mutability> Slice<T, Mutability> {
                 ptr: self.ptr,
                 x: self.x + x,
                 y: self.y + y,
-                width: width,
-                height: height,
+                width,
+                height,
                 _mutability: Immutable { marker: marker::PhantomData },
             })
         } else {
@@ -171,6 +264,14 @@ impl<'a, T, Mutability> Slice<T, Mutability> {
         }
     }
 
+    pub fn row(&self, y: usize) -> Option<ImmutableSlice<'_, T>> {
+        self.as_immutable().row_ref(y)
+    }
+
+    pub fn column(&self, x: usize) -> Option<ImmutableSlice<'_, T>> {
+        self.as_immutable().column_ref(x)
+    }
+
     pub fn hsplit_at(&self, y: usize) -> Option<(ImmutableSlice<'a, T>, ImmutableSlice<'a, T>)> {
         if y <= self.height {
             Some((self.slice(0, 0, self.width, y).unwrap(),
@@ -190,6 +291,46 @@ impl<'a, T, Mutability> Slice<T, Mutability> {
     }
 }
 
+impl<'a, T: 'a> Slice<T, Immutable<'a, T>> {
+    // Like `get`, but the returned reference lives as long as the borrow of the
+    // underlying `VecVec` instead of the borrow of this slice.
+    fn get_ref(&self, x: usize, y: usize) -> Option<&'a T> {
+        if x < self.width && y < self.height {
+            unsafe { (*self.ptr).get(self.x + x, self.y + y) }
+        } else {
+            None
+        }
+    }
+
+    // Like `row`, but the returned slice lives as long as the borrow of the
+    // underlying `VecVec` instead of the borrow of this slice.
+    fn row_ref(&self, y: usize) -> Option<ImmutableSlice<'a, T>> {
+        if y < self.height {
+            self.slice(0, y, self.width, 1)
+        } else {
+            None
+        }
+    }
+
+    // Like `column`, but the returned slice lives as long as the borrow of the
+    // underlying `VecVec` instead of the borrow of this slice.
+    fn column_ref(&self, x: usize) -> Option<ImmutableSlice<'a, T>> {
+        if x < self.width {
+            self.slice(x, 0, 1, self.height)
+        } else {
+            None
+        }
+    }
+
+    // Positions and values of all cells in row-major order.
+    fn cells(&self) -> impl Iterator<Item = ((usize, usize), &'a T)> {
+        let (width, slice) = (self.width, self.slice(0, 0, self.width, self.height).unwrap());
+        (0..self.height)
+            .flat_map(move |y| (0..width).map(move |x| (x, y)))
+            .map(move |(x, y)| ((x, y), slice.get_ref(x, y).unwrap()))
+    }
+}
+
 impl<'a, T: 'a> Slice<T, Mutable<'a, T>> {
     pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
         if x < self.width && y < self.height {
@@ -252,8 +393,8 @@ impl<'a, T: 'a> Slice<T, Mutable<'a, T>> {
                 ptr: self.ptr,
                 x: self.x + x,
                 y: self.y + y,
-                width: width,
-                height: height,
+                width,
+                height,
                 _mutability: Mutable { marker: marker::PhantomData },
             })
         } else {
@@ -279,8 +420,17 @@ impl<T: PartialEq, M1, M2> PartialEq<Slice<T, M2>> for Slice<T, M1> {
     }
 }
 
+/// With the alternate flag, `{:#?}` renders the same table as `{:#}`, with
+/// cells formatted by `Debug`.
 impl<T: fmt::Debug, Mutability> fmt::Debug for Slice<T, Mutability> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            return table::table(self, f, |cell, precision| match precision {
+                Some(precision) => format!("{:.*?}", precision, cell),
+                None => format!("{:?}", cell),
+            });
+        }
+
         struct Row<'a, T: fmt::Debug + 'a, Mutability: 'a>(&'a Slice<T, Mutability>, usize);
 
         impl<'a, T: fmt::Debug, Mutability> fmt::Debug for Row<'a, T, Mutability> {
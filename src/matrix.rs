@@ -0,0 +1,179 @@
+// Dense matrix operations, treating a grid as a matrix with `height` rows and
+// `width` columns.
+
+use {Float, Num, Signed, Slice, VecVec};
+
+impl<T: Num> VecVec<T> {
+    /// The `n x n` identity matrix.
+    pub fn identity(n: usize) -> Self {
+        VecVec::from_fn(n, n, |x, y| if x == y { T::one() } else { T::zero() })
+    }
+}
+
+impl<T: Clone> VecVec<T> {
+    pub fn transpose(&self) -> VecVec<T> {
+        self.as_slice().transpose()
+    }
+}
+
+impl<T: Num> VecVec<T> {
+    pub fn matmul<M>(&self, rhs: &Slice<T, M>) -> Option<VecVec<T>> {
+        self.as_slice().matmul(rhs)
+    }
+
+    pub fn dot<M>(&self, rhs: &Slice<T, M>) -> Option<T> {
+        self.as_slice().dot(rhs)
+    }
+
+    pub fn trace(&self) -> Option<T> {
+        self.as_slice().trace()
+    }
+}
+
+impl<T: Signed> VecVec<T> {
+    pub fn determinant(&self) -> Option<T> {
+        self.as_slice().determinant()
+    }
+}
+
+impl<T: Float> VecVec<T> {
+    pub fn inverse(&self) -> Option<VecVec<T>> {
+        self.as_slice().inverse()
+    }
+}
+
+impl<T: Clone, Mutability> Slice<T, Mutability> {
+    pub fn transpose(&self) -> VecVec<T> {
+        VecVec::from_fn(self.height, self.width, |x, y| self.get(y, x).unwrap().clone())
+    }
+}
+
+impl<T: Num, Mutability> Slice<T, Mutability> {
+    /// The matrix product `self * rhs`, or `None` if the width of `self` is not
+    /// the height of `rhs`.
+    pub fn matmul<M>(&self, rhs: &Slice<T, M>) -> Option<VecVec<T>> {
+        if self.width != rhs.height {
+            return None;
+        }
+        Some(VecVec::from_fn(rhs.width, self.height, |x, y| {
+            (0..self.width).fold(T::zero(), |sum, i| {
+                sum + *self.get(i, y).unwrap() * *rhs.get(x, i).unwrap()
+            })
+        }))
+    }
+
+    /// The dot product of two row or column vectors of the same length, or
+    /// `None` if either is not a vector or the lengths differ.
+    pub fn dot<M>(&self, rhs: &Slice<T, M>) -> Option<T> {
+        if !self.is_vector() || !rhs.is_vector() || self.len() != rhs.len() {
+            return None;
+        }
+        Some((0..self.len()).fold(T::zero(), |sum, i| sum + *self.at(i) * *rhs.at(i)))
+    }
+
+    /// The sum of the main diagonal, or `None` if the matrix is not square.
+    pub fn trace(&self) -> Option<T> {
+        if self.width != self.height {
+            return None;
+        }
+        Some((0..self.width).fold(T::zero(), |sum, i| sum + *self.get(i, i).unwrap()))
+    }
+}
+
+impl<T, Mutability> Slice<T, Mutability> {
+    fn is_vector(&self) -> bool {
+        self.width == 1 || self.height == 1
+    }
+
+    fn len(&self) -> usize {
+        self.width * self.height
+    }
+
+    // Indexes a row or column vector.
+    fn at(&self, i: usize) -> &T {
+        if self.height == 1 {
+            self.get(i, 0).unwrap()
+        } else {
+            self.get(0, i).unwrap()
+        }
+    }
+
+    fn to_rows(&self) -> Vec<Vec<T>>
+        where T: Clone
+    {
+        (0..self.height)
+            .map(|y| (0..self.width).map(|x| self.get(x, y).unwrap().clone()).collect())
+            .collect()
+    }
+}
+
+impl<T: Signed, Mutability> Slice<T, Mutability> {
+    /// The determinant, or `None` if the matrix is not square. Uses the
+    /// fraction-free Bareiss algorithm, so it is exact for integers.
+    pub fn determinant(&self) -> Option<T> {
+        if self.width != self.height {
+            return None;
+        }
+        let n = self.width;
+        let mut m = self.to_rows();
+        let mut sign = T::one();
+        let mut previous = T::one();
+        for k in 0..n {
+            if m[k][k] == T::zero() {
+                match (k + 1..n).find(|&i| m[i][k] != T::zero()) {
+                    Some(i) => {
+                        m.swap(i, k);
+                        sign = -sign;
+                    }
+                    None => return Some(T::zero()),
+                }
+            }
+            for i in k + 1..n {
+                for j in k + 1..n {
+                    m[i][j] = (m[i][j] * m[k][k] - m[i][k] * m[k][j]) / previous;
+                }
+            }
+            previous = m[k][k];
+        }
+        Some(sign * previous)
+    }
+}
+
+impl<T: Float, Mutability> Slice<T, Mutability> {
+    /// The inverse, or `None` if the matrix is not square or is singular. Uses
+    /// Gauss-Jordan elimination with partial pivoting.
+    pub fn inverse(&self) -> Option<VecVec<T>> {
+        if self.width != self.height {
+            return None;
+        }
+        let n = self.width;
+        let abs = |value: T| if value < T::zero() { -value } else { value };
+        let mut m = self.to_rows();
+        let mut inverse = VecVec::<T>::identity(n).as_slice().to_rows();
+        for k in 0..n {
+            let pivot = (k..n).fold(k, |best, i| {
+                if abs(m[i][k]) > abs(m[best][k]) { i } else { best }
+            });
+            if m[pivot][k] == T::zero() {
+                return None;
+            }
+            m.swap(pivot, k);
+            inverse.swap(pivot, k);
+            let scale = m[k][k];
+            for j in 0..n {
+                m[k][j] = m[k][j] / scale;
+                inverse[k][j] = inverse[k][j] / scale;
+            }
+            for i in 0..n {
+                if i != k {
+                    let factor = m[i][k];
+                    for j in 0..n {
+                        m[i][j] = m[i][j] - factor * m[k][j];
+                        inverse[i][j] = inverse[i][j] - factor * inverse[k][j];
+                    }
+                }
+            }
+        }
+        Some(VecVec::from_fn(n, n, |x, y| inverse[y][x]))
+    }
+}
@@ -0,0 +1,184 @@
+use std::slice;
+
+use {Boundary, ImmutableSlice, Slice, VecVec};
+
+const FOUR: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+const EIGHT: [(isize, isize); 8] = [(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1),
+                                    (1, 1)];
+
+/// Which cells count as adjacent to each other.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Connectivity {
+    /// Cells sharing an edge.
+    Four,
+    /// Cells sharing an edge or a corner.
+    Eight,
+}
+
+impl Connectivity {
+    pub fn offsets(&self) -> &'static [(isize, isize)] {
+        match *self {
+            Connectivity::Four => &FOUR,
+            Connectivity::Eight => &EIGHT,
+        }
+    }
+}
+
+/// A set of `(dx, dy)` offsets relative to a cell.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Stencil {
+    offsets: Vec<(isize, isize)>,
+}
+
+impl Stencil {
+    pub fn new(offsets: Vec<(isize, isize)>) -> Self {
+        Stencil { offsets }
+    }
+
+    /// The 4-connected (von Neumann) neighborhood.
+    pub fn four() -> Self {
+        Stencil::new(FOUR.to_vec())
+    }
+
+    /// The 8-connected (Moore) neighborhood.
+    pub fn eight() -> Self {
+        Stencil::new(EIGHT.to_vec())
+    }
+
+    pub fn connectivity(connectivity: Connectivity) -> Self {
+        Stencil::new(connectivity.offsets().to_vec())
+    }
+
+    pub fn offsets(&self) -> &[(isize, isize)] {
+        &self.offsets
+    }
+}
+
+/// Iterator over the cells of a `Stencil` around a position, resolving out of
+/// range cells with a `Boundary`. Yields the position each value was read from,
+/// which lies outside the grid only for `Boundary::Constant`. Positions that do
+/// not fit in an `isize` saturate.
+pub struct Neighbors<'a, T: 'a> {
+    slice: ImmutableSlice<'a, T>,
+    x: usize,
+    y: usize,
+    offsets: slice::Iter<'a, (isize, isize)>,
+    boundary: &'a Boundary<T>,
+}
+
+impl<'a, T> Iterator for Neighbors<'a, T> {
+    type Item = ((isize, isize), &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for &(dx, dy) in &mut self.offsets {
+            // Cannot overflow, so positions past either end of `isize` are
+            // still resolved as out of range by the boundary.
+            let (x, y) = (self.x as i128 + dx as i128, self.y as i128 + dy as i128);
+            match (self.boundary.index(x, self.slice.width),
+                   self.boundary.index(y, self.slice.height)) {
+                (Some(x), Some(y)) => {
+                    let position = (saturate(x as i128), saturate(y as i128));
+                    return Some((position, self.slice.get_ref(x, y).unwrap()));
+                }
+                _ => {
+                    if let Boundary::Constant(ref value) = *self.boundary {
+                        return Some(((saturate(x), saturate(y)), value));
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+fn saturate(i: i128) -> isize {
+    if i < isize::MIN as i128 {
+        isize::MIN
+    } else if i > isize::MAX as i128 {
+        isize::MAX
+    } else {
+        i as isize
+    }
+}
+
+fn neighbors<'a, T>(slice: ImmutableSlice<'a, T>,
+                    x: usize,
+                    y: usize,
+                    stencil: &'a Stencil,
+                    boundary: &'a Boundary<T>)
+                    -> Neighbors<'a, T> {
+    Neighbors {
+        slice,
+        x,
+        y,
+        offsets: stencil.offsets.iter(),
+        boundary,
+    }
+}
+
+// The in range neighbors of `(x, y)` in a `width x height` grid.
+pub(crate) fn adjacent(x: usize,
+                       y: usize,
+                       width: usize,
+                       height: usize,
+                       connectivity: Connectivity)
+                       -> impl Iterator<Item = (usize, usize)> {
+    connectivity.offsets().iter().filter_map(move |&(dx, dy)| {
+        let (x, y) = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        if x < width && y < height {
+            Some((x, y))
+        } else {
+            None
+        }
+    })
+}
+
+fn in_bounds<'a, T: 'a>(slice: ImmutableSlice<'a, T>,
+                        x: usize,
+                        y: usize,
+                        offsets: &'static [(isize, isize)])
+                        -> impl Iterator<Item = ((usize, usize), &'a T)> + 'a {
+    offsets.iter().filter_map(move |&(dx, dy)| {
+        let (x, y) = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        slice.get_ref(x, y).map(|value| ((x, y), value))
+    })
+}
+
+impl<T> VecVec<T> {
+    pub fn neighbors4(&self, x: usize, y: usize) -> impl Iterator<Item = ((usize, usize), &T)> {
+        in_bounds(self.as_slice(), x, y, &FOUR)
+    }
+
+    pub fn neighbors8(&self, x: usize, y: usize) -> impl Iterator<Item = ((usize, usize), &T)> {
+        in_bounds(self.as_slice(), x, y, &EIGHT)
+    }
+
+    pub fn neighbors<'a>(&'a self,
+                         x: usize,
+                         y: usize,
+                         stencil: &'a Stencil,
+                         boundary: &'a Boundary<T>)
+                         -> Neighbors<'a, T> {
+        neighbors(self.as_slice(), x, y, stencil, boundary)
+    }
+}
+
+impl<T, Mutability> Slice<T, Mutability> {
+    pub fn neighbors4(&self, x: usize, y: usize) -> impl Iterator<Item = ((usize, usize), &T)> {
+        in_bounds(self.as_immutable(), x, y, &FOUR)
+    }
+
+    pub fn neighbors8(&self, x: usize, y: usize) -> impl Iterator<Item = ((usize, usize), &T)> {
+        in_bounds(self.as_immutable(), x, y, &EIGHT)
+    }
+
+    pub fn neighbors<'a>(&'a self,
+                         x: usize,
+                         y: usize,
+                         stencil: &'a Stencil,
+                         boundary: &'a Boundary<T>)
+                         -> Neighbors<'a, T> {
+        neighbors(self.as_immutable(), x, y, stencil, boundary)
+    }
+}
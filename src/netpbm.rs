@@ -0,0 +1,395 @@
+//! Reading and writing Netpbm images: PBM (P1, P4), PGM (P2, P5), PPM (P3, P6)
+//! and PAM (P7).
+//!
+//! Element types choose the kind of image through `Pixel`: `bool` for bitmaps,
+//! `u8` and `u16` for graymaps, `[u8; 3]` and `[u16; 3]` for pixmaps, and
+//! `[u8; 4]` and `[u16; 4]` for PAM images with alpha. `bool` cells are `true`
+//! for black in every format, so are stored as 1 in PBM but as 0 in PAM, where
+//! 1 is white.
+
+use std::io::{self, Read, Write};
+
+use bytes::invalid_data;
+use {Slice, VecVec};
+
+/// Element types that can be stored in a Netpbm image.
+pub trait Pixel: Copy {
+    /// The number of channels.
+    const DEPTH: usize;
+    /// The largest value of a channel.
+    const MAXVAL: u16;
+    /// The PAM tuple type.
+    const TUPLTYPE: &'static str;
+
+    fn channel(&self, i: usize) -> u16;
+    fn from_channels(channels: &[u16]) -> Self;
+}
+
+impl Pixel for bool {
+    const DEPTH: usize = 1;
+    const MAXVAL: u16 = 1;
+    const TUPLTYPE: &'static str = "BLACKANDWHITE";
+
+    fn channel(&self, _: usize) -> u16 {
+        *self as u16
+    }
+
+    fn from_channels(channels: &[u16]) -> Self {
+        channels[0] != 0
+    }
+}
+
+macro_rules! pixel {
+    ($ty:ty, $maxval:expr, $depth:expr, $tupltype:expr) => {
+        impl Pixel for [$ty; $depth] {
+            const DEPTH: usize = $depth;
+            const MAXVAL: u16 = $maxval;
+            const TUPLTYPE: &'static str = $tupltype;
+
+            fn channel(&self, i: usize) -> u16 {
+                self[i] as u16
+            }
+
+            fn from_channels(channels: &[u16]) -> Self {
+                let mut pixel = [0; $depth];
+                for (pixel, &channel) in pixel.iter_mut().zip(channels) {
+                    *pixel = channel as $ty;
+                }
+                pixel
+            }
+        }
+    };
+    ($ty:ty, $maxval:expr) => {
+        impl Pixel for $ty {
+            const DEPTH: usize = 1;
+            const MAXVAL: u16 = $maxval;
+            const TUPLTYPE: &'static str = "GRAYSCALE";
+
+            fn channel(&self, _: usize) -> u16 {
+                *self as u16
+            }
+
+            fn from_channels(channels: &[u16]) -> Self {
+                channels[0] as $ty
+            }
+        }
+
+        pixel!($ty, $maxval, 3, "RGB");
+        pixel!($ty, $maxval, 4, "RGB_ALPHA");
+    }
+}
+
+pixel!(u8, 255);
+pixel!(u16, 65535);
+
+/// How samples of PBM, PGM and PPM images are stored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Encoding {
+    /// Decimal text: P1, P2 and P3.
+    Ascii,
+    /// Raw bytes: P4, P5 and P6.
+    Binary,
+}
+
+/// Writes `slice` as a PBM, PGM or PPM image depending on the depth and maximum
+/// value of `T`. Fails with `InvalidInput` for types with 4 channels, which
+/// only PAM supports.
+pub fn write<T, M, W>(slice: &Slice<T, M>, mut writer: W, encoding: Encoding) -> io::Result<()>
+    where T: Pixel,
+          W: Write
+{
+    let (width, height) = (slice.width(), slice.height());
+    let bitmap = T::MAXVAL == 1;
+    let magic = match (T::DEPTH, bitmap, encoding) {
+        (1, true, Encoding::Ascii) => "P1",
+        (1, false, Encoding::Ascii) => "P2",
+        (3, _, Encoding::Ascii) => "P3",
+        (1, true, Encoding::Binary) => "P4",
+        (1, false, Encoding::Binary) => "P5",
+        (3, _, Encoding::Binary) => "P6",
+        _ => {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                      "only PAM supports pixels with 4 channels"))
+        }
+    };
+    write!(writer, "{}\n{} {}\n", magic, width, height)?;
+    if !bitmap {
+        writeln!(writer, "{}", T::MAXVAL)?;
+    }
+    match encoding {
+        Encoding::Ascii => {
+            for y in 0..height {
+                let mut line = Vec::new();
+                for x in 0..width {
+                    let pixel = slice.get(x, y).unwrap();
+                    line.extend((0..T::DEPTH).map(|i| pixel.channel(i).to_string()));
+                }
+                writeln!(writer, "{}", line.join(" "))?;
+            }
+        }
+        Encoding::Binary if bitmap => {
+            for y in 0..height {
+                let mut row = vec![0u8; width.div_ceil(8)];
+                for x in 0..width {
+                    if slice.get(x, y).unwrap().channel(0) != 0 {
+                        row[x / 8] |= 0x80 >> (x % 8);
+                    }
+                }
+                writer.write_all(&row)?;
+            }
+        }
+        Encoding::Binary => write_samples(slice, &mut writer, false)?,
+    }
+    Ok(())
+}
+
+/// Writes `slice` as a PAM image.
+pub fn write_pam<T, M, W>(slice: &Slice<T, M>, mut writer: W) -> io::Result<()>
+    where T: Pixel,
+          W: Write
+{
+    write!(writer,
+           "P7\nWIDTH {}\nHEIGHT {}\nDEPTH {}\nMAXVAL {}\nTUPLTYPE {}\nENDHDR\n",
+           slice.width(),
+           slice.height(),
+           T::DEPTH,
+           T::MAXVAL,
+           T::TUPLTYPE)?;
+    // PAM bitmaps use 1 for white, the opposite of PBM.
+    write_samples(slice, &mut writer, T::MAXVAL == 1)
+}
+
+// Writes every channel of every pixel in one byte, or two big-endian bytes if
+// the maximum value does not fit in one. Writes `MAXVAL - sample` instead if
+// `invert` is set.
+fn write_samples<T: Pixel, M, W: Write>(slice: &Slice<T, M>,
+                                        writer: &mut W,
+                                        invert: bool)
+                                        -> io::Result<()> {
+    let mut row = Vec::new();
+    for y in 0..slice.height() {
+        row.clear();
+        for x in 0..slice.width() {
+            let pixel = slice.get(x, y).unwrap();
+            for i in 0..T::DEPTH {
+                let sample = if invert {
+                    T::MAXVAL - pixel.channel(i)
+                } else {
+                    pixel.channel(i)
+                };
+                if T::MAXVAL > 255 {
+                    row.push((sample >> 8) as u8);
+                }
+                row.push(sample as u8);
+            }
+        }
+        writer.write_all(&row)?;
+    }
+    Ok(())
+}
+
+struct Parser<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn skip_whitespace(&mut self) {
+        while let Some(&byte) = self.data.get(self.position) {
+            if byte == b'#' {
+                while self.position < self.data.len() && self.data[self.position] != b'\n' {
+                    self.position += 1;
+                }
+            } else if byte.is_ascii_whitespace() {
+                self.position += 1;
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn token(&mut self) -> io::Result<&'a str> {
+        self.skip_whitespace();
+        let start = self.position;
+        while self.position < self.data.len() && !self.data[self.position].is_ascii_whitespace() {
+            self.position += 1;
+        }
+        if start == self.position {
+            return Err(invalid_data("unexpected end of header"));
+        }
+        ::std::str::from_utf8(&self.data[start..self.position]).map_err(invalid_data)
+    }
+
+    fn number(&mut self) -> io::Result<usize> {
+        let token = self.token()?;
+        token.parse().map_err(|_| invalid_data(format!("invalid number {:?}", token)))
+    }
+
+    fn maxval(&mut self) -> io::Result<u16> {
+        match self.number()? {
+            maxval @ 1..=0xffff => Ok(maxval as u16),
+            maxval => Err(invalid_data(format!("invalid maximum value {}", maxval))),
+        }
+    }
+
+    fn sample(&mut self, maxval: u16) -> io::Result<u16> {
+        let sample = self.number()?;
+        if sample > maxval as usize {
+            return Err(invalid_data("sample exceeds maximum value"));
+        }
+        Ok(sample as u16)
+    }
+
+    fn bit(&mut self) -> io::Result<u16> {
+        self.skip_whitespace();
+        let bit = match self.data.get(self.position) {
+            Some(&b'0') => 0,
+            Some(&b'1') => 1,
+            _ => return Err(invalid_data("invalid PBM sample")),
+        };
+        self.position += 1;
+        Ok(bit)
+    }
+
+    fn bytes(&mut self, count: usize) -> io::Result<&'a [u8]> {
+        let end = self.position.checked_add(count).filter(|&end| end <= self.data.len());
+        let end = end.ok_or_else(|| invalid_data("unexpected end of image data"))?;
+        let bytes = &self.data[self.position..end];
+        self.position = end;
+        Ok(bytes)
+    }
+}
+
+struct Header {
+    width: usize,
+    height: usize,
+    depth: usize,
+    maxval: u16,
+}
+
+fn pam_header(parser: &mut Parser) -> io::Result<Header> {
+    let (mut width, mut height, mut depth, mut maxval) = (None, None, None, None);
+    loop {
+        match parser.token()? {
+            "WIDTH" => width = Some(parser.number()?),
+            "HEIGHT" => height = Some(parser.number()?),
+            "DEPTH" => depth = Some(parser.number()?),
+            "MAXVAL" => maxval = Some(parser.maxval()?),
+            "TUPLTYPE" => {
+                parser.token()?;
+            }
+            "ENDHDR" => break,
+            token => return Err(invalid_data(format!("unknown PAM header field {:?}", token))),
+        }
+    }
+    match (width, height, depth, maxval) {
+        (Some(width), Some(height), Some(depth), Some(maxval)) => {
+            Ok(Header {
+                width,
+                height,
+                depth,
+                maxval,
+            })
+        }
+        _ => Err(invalid_data("missing PAM header field")),
+    }
+}
+
+/// Reads a Netpbm image of any format into a grid of `T`. Bitmaps can only be
+/// read as `bool` and graymaps and pixmaps only as types with the same number
+/// of channels; samples are rescaled from the maximum value of the image to
+/// that of `T`.
+pub fn read<T: Pixel, R: Read>(mut reader: R) -> io::Result<VecVec<T>> {
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data)?;
+    let mut parser = Parser {
+        data: &data,
+        position: 0,
+    };
+    let magic = parser.token()?;
+    let header = match magic {
+        "P1" | "P2" | "P3" | "P4" | "P5" | "P6" => {
+            let width = parser.number()?;
+            let height = parser.number()?;
+            let maxval = match magic {
+                "P1" | "P4" => 1,
+                _ => parser.maxval()?,
+            };
+            let depth = if magic == "P3" || magic == "P6" { 3 } else { 1 };
+            Header {
+                width,
+                height,
+                depth,
+                maxval,
+            }
+        }
+        "P7" => pam_header(&mut parser)?,
+        _ => return Err(invalid_data(format!("unknown magic number {:?}", magic))),
+    };
+    if header.depth != T::DEPTH || (header.maxval == 1) != (T::MAXVAL == 1) {
+        return Err(invalid_data(format!("cannot read an image of depth {} and maximum value \
+                                          {} into pixels of depth {} and maximum value {}",
+                                         header.depth,
+                                         header.maxval,
+                                         T::DEPTH,
+                                         T::MAXVAL)));
+    }
+    let count = header.width
+        .checked_mul(header.height)
+        .and_then(|count| count.checked_mul(header.depth))
+        .ok_or_else(|| invalid_data("image too large"))?;
+
+    let mut samples = Vec::new();
+    match magic {
+        "P1" => {
+            for _ in 0..count {
+                samples.push(parser.bit()?);
+            }
+        }
+        "P2" | "P3" => {
+            for _ in 0..count {
+                samples.push(parser.sample(header.maxval)?);
+            }
+        }
+        _ => {
+            // A single whitespace byte separates the header from the data.
+            parser.position += 1;
+            if magic == "P4" {
+                let stride = header.width.div_ceil(8);
+                for _ in 0..header.height {
+                    let row = parser.bytes(stride)?;
+                    samples.extend((0..header.width)
+                        .map(|x| (row[x / 8] >> (7 - x % 8)) as u16 & 1));
+                }
+            } else {
+                let size = if header.maxval > 255 { 2 } else { 1 };
+                let bytes = parser.bytes(count.saturating_mul(size))?;
+                samples.extend(bytes.chunks(size).map(|sample| {
+                    sample.iter().fold(0, |value, &byte| value << 8 | byte as u16)
+                }));
+            }
+        }
+    }
+
+    if samples.iter().any(|&sample| sample > header.maxval) {
+        return Err(invalid_data("sample exceeds maximum value"));
+    }
+    // PAM bitmaps use 1 for white, the opposite of PBM and `bool`.
+    if magic == "P7" && header.maxval == 1 {
+        for sample in &mut samples {
+            *sample = 1 - *sample;
+        }
+    }
+    if header.maxval != T::MAXVAL {
+        for sample in &mut samples {
+            let scaled = (*sample as u32 * T::MAXVAL as u32 + header.maxval as u32 / 2) /
+                         header.maxval as u32;
+            *sample = scaled as u16;
+        }
+    }
+    Ok(VecVec {
+        inner: samples.chunks(T::DEPTH).map(T::from_channels).collect(),
+        width: header.width,
+        height: header.height,
+    })
+}
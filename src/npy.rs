@@ -0,0 +1,203 @@
+//! Reading and writing NumPy `.npy` files holding 2-dimensional arrays, with
+//! `VecVec::read_npy` and `write_npy`. Arrays have shape `(height, width)`.
+
+use std::io::{self, Read, Write};
+
+use bytes::{invalid_data, Pod};
+use {Slice, VecVec};
+
+const MAGIC: &[u8] = b"\x93NUMPY";
+
+/// Element types that can be stored in a `.npy` file.
+pub trait Element: Pod {
+    /// The type code and size of the dtype, like `"i4"`.
+    const DTYPE: &'static str;
+}
+
+macro_rules! element {
+    ($($ty:ty => $dtype:expr),*) => {
+        $(
+            impl Element for $ty {
+                const DTYPE: &'static str = $dtype;
+            }
+        )*
+    }
+}
+
+element!(bool => "b1",
+         u8 => "u1", u16 => "u2", u32 => "u4", u64 => "u8",
+         i8 => "i1", i16 => "i2", i32 => "i4", i64 => "i8",
+         f32 => "f4", f64 => "f8");
+
+// The text following `'key':` in a header dictionary.
+fn value<'a>(header: &'a str, key: &str) -> io::Result<&'a str> {
+    let pattern = format!("'{}':", key);
+    match header.find(&pattern) {
+        Some(start) => Ok(header[start + pattern.len()..].trim_start()),
+        None => Err(invalid_data(format!("missing {:?} in header", key))),
+    }
+}
+
+struct Header {
+    dtype: String,
+    fortran_order: bool,
+    shape: Vec<usize>,
+}
+
+fn parse_header(header: &str) -> io::Result<Header> {
+    let descr = value(header, "descr")?;
+    let dtype = descr.chars()
+        .next()
+        .filter(|&quote| quote == '\'' || quote == '"')
+        .and_then(|quote| descr[1..].find(quote).map(|end| &descr[1..end + 1]))
+        .ok_or_else(|| invalid_data("invalid descr in header"))?;
+
+    let fortran_order = value(header, "fortran_order")?;
+    let fortran_order = if fortran_order.starts_with("True") {
+        true
+    } else if fortran_order.starts_with("False") {
+        false
+    } else {
+        return Err(invalid_data("invalid fortran_order in header"));
+    };
+
+    let shape = value(header, "shape")?;
+    let shape = shape.strip_prefix('(')
+        .and_then(|shape| shape.find(')').map(|end| &shape[..end]))
+        .ok_or_else(|| invalid_data("invalid shape in header"))?;
+    let shape = shape.split(',')
+        .map(str::trim)
+        .filter(|dimension| !dimension.is_empty())
+        .map(|dimension| dimension.parse().map_err(|_| invalid_data("invalid shape in header")))
+        .collect::<io::Result<_>>()?;
+
+    Ok(Header {
+        dtype: dtype.to_string(),
+        fortran_order,
+        shape,
+    })
+}
+
+fn read<T: Element, R: Read>(mut reader: R) -> io::Result<VecVec<T>> {
+    let mut preamble = [0; 8];
+    reader.read_exact(&mut preamble)?;
+    if &preamble[..6] != MAGIC {
+        return Err(invalid_data("not a .npy file"));
+    }
+    let header_len = match preamble[6] {
+        1 => {
+            let mut len = [0; 2];
+            reader.read_exact(&mut len)?;
+            u16::from_le_bytes(len) as usize
+        }
+        2 | 3 => {
+            let mut len = [0; 4];
+            reader.read_exact(&mut len)?;
+            u32::from_le_bytes(len) as usize
+        }
+        version => return Err(invalid_data(format!("unsupported .npy version {}", version))),
+    };
+    let mut header = Vec::new();
+    reader.by_ref().take(header_len as u64).read_to_end(&mut header)?;
+    let header = String::from_utf8(header).map_err(invalid_data)?;
+    let header = parse_header(&header)?;
+
+    let (height, width) = match header.shape[..] {
+        [height, width] => (height, width),
+        ref shape => {
+            return Err(invalid_data(format!("expected a 2-dimensional array, found shape {:?}",
+                                            shape)))
+        }
+    };
+    let (order, dtype) = header.dtype.split_at(header.dtype.len().min(1));
+    let little_endian = match order {
+        "<" | "|" => true,
+        ">" => false,
+        "=" => cfg!(target_endian = "little"),
+        _ => return Err(invalid_data(format!("invalid dtype {:?}", header.dtype))),
+    };
+    if dtype != T::DTYPE {
+        return Err(invalid_data(format!("expected dtype {:?}, found {:?}",
+                                        T::DTYPE,
+                                        header.dtype)));
+    }
+
+    let size = T::SIZE;
+    let len = width.checked_mul(height)
+        .and_then(|count| count.checked_mul(size))
+        .ok_or_else(|| invalid_data("array too large"))?;
+    let mut data = Vec::new();
+    reader.take(len as u64).read_to_end(&mut data)?;
+    if data.len() != len {
+        return Err(invalid_data("unexpected end of array data"));
+    }
+    let at = |i: usize| {
+        let bytes = &data[i * size..(i + 1) * size];
+        if little_endian {
+            T::from_le_bytes(bytes)
+        } else {
+            T::from_be_bytes(bytes)
+        }
+    };
+    Ok(if header.fortran_order {
+        VecVec::from_fn(width, height, |x, y| at(x * height + y))
+    } else {
+        VecVec::from_fn(width, height, |x, y| at(y * width + x))
+    })
+}
+
+fn write<T: Element, M, W: Write>(slice: &Slice<T, M>, mut writer: W) -> io::Result<()> {
+    let order = if T::SIZE == 1 { '|' } else { '<' };
+    let mut header = format!("{{'descr': '{}{}', 'fortran_order': False, 'shape': ({}, {}), }}",
+                             order,
+                             T::DTYPE,
+                             slice.height(),
+                             slice.width());
+    // The header ends with a newline and is padded with spaces so that the data
+    // starts at a multiple of 64 bytes.
+    let prefix = if header.len() + 11 <= u16::MAX as usize { 10 } else { 12 };
+    let padding = (64 - (prefix + header.len() + 1) % 64) % 64;
+    header.extend((0..padding).map(|_| ' '));
+    header.push('\n');
+
+    writer.write_all(MAGIC)?;
+    if prefix == 10 {
+        writer.write_all(&[1, 0])?;
+        writer.write_all(&(header.len() as u16).to_le_bytes())?;
+    } else {
+        writer.write_all(&[2, 0])?;
+        writer.write_all(&(header.len() as u32).to_le_bytes())?;
+    }
+    writer.write_all(header.as_bytes())?;
+
+    let mut row = Vec::new();
+    for y in 0..slice.height() {
+        row.clear();
+        for x in 0..slice.width() {
+            slice.get(x, y).unwrap().extend_le_bytes(&mut row);
+        }
+        writer.write_all(&row)?;
+    }
+    Ok(())
+}
+
+impl<T: Element> VecVec<T> {
+    /// Reads a 2-dimensional array in either C or Fortran order. Fails with
+    /// `InvalidData` if the array has a different number of dimensions or a
+    /// dtype other than that of `T`.
+    pub fn read_npy<R: Read>(reader: R) -> io::Result<Self> {
+        read(reader)
+    }
+
+    /// Writes a little-endian array in C order.
+    pub fn write_npy<W: Write>(&self, writer: W) -> io::Result<()> {
+        write(&self.as_slice(), writer)
+    }
+}
+
+impl<T: Element, Mutability> Slice<T, Mutability> {
+    /// Writes a little-endian array in C order.
+    pub fn write_npy<W: Write>(&self, writer: W) -> io::Result<()> {
+        write(self, writer)
+    }
+}
@@ -0,0 +1,73 @@
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Numeric element types supported by the arithmetic in this crate.
+pub trait Num: Copy + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+{
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn to_f64(self) -> f64;
+}
+
+/// `Num`s that can be negated.
+pub trait Signed: Num + Neg<Output = Self> {}
+
+/// Floating point `Num`s.
+pub trait Float: Signed {
+    fn from_f64(value: f64) -> Self;
+    fn sqrt(self) -> Self;
+    fn exp(self) -> Self;
+}
+
+macro_rules! num {
+    ($($ty:ty),*) => {
+        $(
+            impl Num for $ty {
+                fn zero() -> Self {
+                    0 as $ty
+                }
+
+                fn one() -> Self {
+                    1 as $ty
+                }
+
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )*
+    }
+}
+
+macro_rules! signed {
+    ($($ty:ty),*) => {
+        $(impl Signed for $ty {})*
+    }
+}
+
+macro_rules! float {
+    ($($ty:ident),*) => {
+        $(
+            impl Float for $ty {
+                fn from_f64(value: f64) -> Self {
+                    value as $ty
+                }
+
+                fn sqrt(self) -> Self {
+                    $ty::sqrt(self)
+                }
+
+                fn exp(self) -> Self {
+                    $ty::exp(self)
+                }
+            }
+        )*
+    }
+}
+
+num!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64);
+signed!(i8, i16, i32, i64, isize, f32, f64);
+float!(f32, f64);
@@ -0,0 +1,206 @@
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use {Mutable, MutableSlice, Slice, VecVec};
+
+const MISMATCH: &str = "grid dimensions do not match";
+
+fn zip_with<T, M1, M2, F>(lhs: &Slice<T, M1>, rhs: &Slice<T, M2>, mut f: F) -> Option<VecVec<T>>
+    where F: FnMut(T, T) -> T,
+          T: Clone
+{
+    if lhs.width() == rhs.width() && lhs.height() == rhs.height() {
+        Some(VecVec::from_fn(lhs.width(), lhs.height(), |x, y| {
+            f(lhs.get(x, y).unwrap().clone(), rhs.get(x, y).unwrap().clone())
+        }))
+    } else {
+        None
+    }
+}
+
+fn zip_assign<T, M, F>(lhs: &mut MutableSlice<T>, rhs: &Slice<T, M>, mut f: F)
+    where F: FnMut(&mut T, T),
+          T: Clone
+{
+    assert!(lhs.width() == rhs.width() && lhs.height() == rhs.height(),
+            "{}",
+            MISMATCH);
+    for y in 0..lhs.height() {
+        for x in 0..lhs.width() {
+            f(lhs.get_mut(x, y).unwrap(), rhs.get(x, y).unwrap().clone());
+        }
+    }
+}
+
+fn map<T, M, F>(slice: &Slice<T, M>, mut f: F) -> VecVec<T>
+    where F: FnMut(T) -> T,
+          T: Clone
+{
+    VecVec::from_fn(slice.width(),
+                    slice.height(),
+                    |x, y| f(slice.get(x, y).unwrap().clone()))
+}
+
+macro_rules! binop {
+    ($Op:ident, $op:ident, $OpAssign:ident, $op_assign:ident, $try_op:ident) => {
+        impl<T: Clone + $Op<Output = T>> VecVec<T> {
+            /// Applies the operator element-wise, or returns `None` if the
+            /// dimensions do not match.
+            pub fn $try_op<M>(&self, rhs: &Slice<T, M>) -> Option<VecVec<T>> {
+                self.as_slice().$try_op(rhs)
+            }
+        }
+
+        impl<T: Clone + $Op<Output = T>, M1> Slice<T, M1> {
+            /// Applies the operator element-wise, or returns `None` if the
+            /// dimensions do not match.
+            pub fn $try_op<M2>(&self, rhs: &Slice<T, M2>) -> Option<VecVec<T>> {
+                zip_with(self, rhs, $Op::$op)
+            }
+        }
+
+        impl<'a, 'b, T: Clone + $Op<Output = T>> $Op<&'b VecVec<T>> for &'a VecVec<T> {
+            type Output = VecVec<T>;
+
+            fn $op(self, rhs: &'b VecVec<T>) -> VecVec<T> {
+                self.$try_op(&rhs.as_slice()).expect(MISMATCH)
+            }
+        }
+
+        impl<'a, 'b, T: Clone + $Op<Output = T>, M> $Op<&'b Slice<T, M>> for &'a VecVec<T> {
+            type Output = VecVec<T>;
+
+            fn $op(self, rhs: &'b Slice<T, M>) -> VecVec<T> {
+                self.$try_op(rhs).expect(MISMATCH)
+            }
+        }
+
+        impl<'a, 'b, T: Clone + $Op<Output = T>, M> $Op<&'b VecVec<T>> for &'a Slice<T, M> {
+            type Output = VecVec<T>;
+
+            fn $op(self, rhs: &'b VecVec<T>) -> VecVec<T> {
+                self.$try_op(&rhs.as_slice()).expect(MISMATCH)
+            }
+        }
+
+        impl<'a, 'b, T: Clone + $Op<Output = T>, M1, M2> $Op<&'b Slice<T, M2>> for &'a Slice<T, M1> {
+            type Output = VecVec<T>;
+
+            fn $op(self, rhs: &'b Slice<T, M2>) -> VecVec<T> {
+                self.$try_op(rhs).expect(MISMATCH)
+            }
+        }
+
+        impl<'b, T: Clone + $Op<Output = T>> $Op<&'b VecVec<T>> for VecVec<T> {
+            type Output = VecVec<T>;
+
+            fn $op(mut self, rhs: &'b VecVec<T>) -> VecVec<T> {
+                $OpAssign::$op_assign(&mut self, rhs);
+                self
+            }
+        }
+
+        impl<'b, T: Clone + $Op<Output = T>, M> $Op<&'b Slice<T, M>> for VecVec<T> {
+            type Output = VecVec<T>;
+
+            fn $op(mut self, rhs: &'b Slice<T, M>) -> VecVec<T> {
+                $OpAssign::$op_assign(&mut self, rhs);
+                self
+            }
+        }
+
+        impl<'a, T: Clone + $Op<Output = T>> $Op<T> for &'a VecVec<T> {
+            type Output = VecVec<T>;
+
+            fn $op(self, rhs: T) -> VecVec<T> {
+                self.as_slice().$op(rhs)
+            }
+        }
+
+        impl<T: Clone + $Op<Output = T>> $Op<T> for VecVec<T> {
+            type Output = VecVec<T>;
+
+            fn $op(mut self, rhs: T) -> VecVec<T> {
+                $OpAssign::$op_assign(&mut self, rhs);
+                self
+            }
+        }
+
+        impl<'a, T: Clone + $Op<Output = T>, M> $Op<T> for &'a Slice<T, M> {
+            type Output = VecVec<T>;
+
+            fn $op(self, rhs: T) -> VecVec<T> {
+                map(self, |value| $Op::$op(value, rhs.clone()))
+            }
+        }
+
+        impl<'b, T: Clone + $Op<Output = T>> $OpAssign<&'b VecVec<T>> for VecVec<T> {
+            fn $op_assign(&mut self, rhs: &'b VecVec<T>) {
+                self.as_mut_slice().$op_assign(&rhs.as_slice())
+            }
+        }
+
+        impl<'b, T: Clone + $Op<Output = T>, M> $OpAssign<&'b Slice<T, M>> for VecVec<T> {
+            fn $op_assign(&mut self, rhs: &'b Slice<T, M>) {
+                self.as_mut_slice().$op_assign(rhs)
+            }
+        }
+
+        impl<T: Clone + $Op<Output = T>> $OpAssign<T> for VecVec<T> {
+            fn $op_assign(&mut self, rhs: T) {
+                self.as_mut_slice().$op_assign(rhs)
+            }
+        }
+
+        impl<'a, 'b, T: Clone + $Op<Output = T>> $OpAssign<&'b VecVec<T>> for Slice<T, Mutable<'a, T>> {
+            fn $op_assign(&mut self, rhs: &'b VecVec<T>) {
+                self.$op_assign(&rhs.as_slice())
+            }
+        }
+
+        impl<'a, 'b, T: Clone + $Op<Output = T>, M> $OpAssign<&'b Slice<T, M>> for Slice<T, Mutable<'a, T>> {
+            fn $op_assign(&mut self, rhs: &'b Slice<T, M>) {
+                zip_assign(self, rhs, |lhs, rhs| *lhs = $Op::$op(lhs.clone(), rhs))
+            }
+        }
+
+        impl<'a, T: Clone + $Op<Output = T>> $OpAssign<T> for Slice<T, Mutable<'a, T>> {
+            fn $op_assign(&mut self, rhs: T) {
+                for y in 0..self.height() {
+                    for x in 0..self.width() {
+                        let value = self.get_mut(x, y).unwrap();
+                        *value = $Op::$op(value.clone(), rhs.clone());
+                    }
+                }
+            }
+        }
+    }
+}
+
+binop!(Add, add, AddAssign, add_assign, try_add);
+binop!(Sub, sub, SubAssign, sub_assign, try_sub);
+binop!(Mul, mul, MulAssign, mul_assign, try_mul);
+binop!(Div, div, DivAssign, div_assign, try_div);
+
+impl<T: Clone + Neg<Output = T>> Neg for &VecVec<T> {
+    type Output = VecVec<T>;
+
+    fn neg(self) -> VecVec<T> {
+        -&self.as_slice()
+    }
+}
+
+impl<T: Clone + Neg<Output = T>> Neg for VecVec<T> {
+    type Output = VecVec<T>;
+
+    fn neg(self) -> VecVec<T> {
+        -&self
+    }
+}
+
+impl<T: Clone + Neg<Output = T>, M> Neg for &Slice<T, M> {
+    type Output = VecVec<T>;
+
+    fn neg(self) -> VecVec<T> {
+        map(self, Neg::neg)
+    }
+}
@@ -0,0 +1,189 @@
+//! Shortest paths over grids.
+//!
+//! Paths are returned as the list of positions from `start` to `goal`
+//! inclusive. Moves go between cells adjacent under the given `Connectivity`.
+//! The cost of a path is the sum of the costs of the cells it enters, so the
+//! cost of `start` itself is never consulted.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
+use neighbors::adjacent;
+use {Connectivity, Slice, VecVec};
+
+type Previous = VecVec<Option<(usize, usize)>>;
+
+fn in_range<T, M>(grid: &Slice<T, M>, (x, y): (usize, usize)) -> bool {
+    x < grid.width() && y < grid.height()
+}
+
+fn walk_back(previous: &Previous, goal: (usize, usize)) -> Vec<(usize, usize)> {
+    let mut path = vec![goal];
+    let mut position = goal;
+    while let Some(next) = *previous.get(position.0, position.1).unwrap() {
+        path.push(next);
+        position = next;
+    }
+    path.reverse();
+    path
+}
+
+fn breadth_first<T, M, F>(grid: &Slice<T, M>,
+                          start: (usize, usize),
+                          goal: Option<(usize, usize)>,
+                          connectivity: Connectivity,
+                          mut passable: F)
+                          -> (VecVec<Option<u32>>, Previous)
+    where F: FnMut(&T) -> bool
+{
+    let (width, height) = (grid.width(), grid.height());
+    let mut distances = VecVec::new(width, height, None);
+    let mut previous = VecVec::new(width, height, None);
+    if !in_range(grid, start) {
+        return (distances, previous);
+    }
+    let mut queue = VecDeque::new();
+    *distances.get_mut(start.0, start.1).unwrap() = Some(0);
+    queue.push_back(start);
+    while let Some((x, y)) = queue.pop_front() {
+        if Some((x, y)) == goal {
+            break;
+        }
+        let distance = distances.get(x, y).unwrap().unwrap();
+        for (nx, ny) in adjacent(x, y, width, height, connectivity) {
+            if distances.get(nx, ny).unwrap().is_none() && passable(grid.get(nx, ny).unwrap()) {
+                *distances.get_mut(nx, ny).unwrap() = Some(distance + 1);
+                *previous.get_mut(nx, ny).unwrap() = Some((x, y));
+                queue.push_back((nx, ny));
+            }
+        }
+    }
+    (distances, previous)
+}
+
+fn best_first<T, M, F, H>(grid: &Slice<T, M>,
+                          start: (usize, usize),
+                          goal: Option<(usize, usize)>,
+                          connectivity: Connectivity,
+                          mut cost: F,
+                          heuristic: H)
+                          -> (VecVec<Option<u32>>, Previous)
+    where F: FnMut(&T) -> Option<u32>,
+          H: Fn(usize, usize) -> u32
+{
+    let (width, height) = (grid.width(), grid.height());
+    let mut distances = VecVec::new(width, height, None);
+    let mut previous = VecVec::new(width, height, None);
+    if !in_range(grid, start) {
+        return (distances, previous);
+    }
+    let mut done = VecVec::new(width, height, false);
+    let mut heap = BinaryHeap::new();
+    *distances.get_mut(start.0, start.1).unwrap() = Some(0);
+    heap.push(Reverse((heuristic(start.0, start.1), 0u32, start)));
+    while let Some(Reverse((_, distance, (x, y)))) = heap.pop() {
+        if *done.get(x, y).unwrap() {
+            continue;
+        }
+        *done.get_mut(x, y).unwrap() = true;
+        if Some((x, y)) == goal {
+            break;
+        }
+        for (nx, ny) in adjacent(x, y, width, height, connectivity) {
+            if *done.get(nx, ny).unwrap() {
+                continue;
+            }
+            if let Some(step) = cost(grid.get(nx, ny).unwrap()) {
+                let next = distance.saturating_add(step);
+                if distances.get(nx, ny).unwrap().is_none_or(|old| next < old) {
+                    *distances.get_mut(nx, ny).unwrap() = Some(next);
+                    *previous.get_mut(nx, ny).unwrap() = Some((x, y));
+                    heap.push(Reverse((next.saturating_add(heuristic(nx, ny)), next, (nx, ny))));
+                }
+            }
+        }
+    }
+    (distances, previous)
+}
+
+fn path((distances, previous): (VecVec<Option<u32>>, Previous),
+        goal: (usize, usize))
+        -> Option<Vec<(usize, usize)>> {
+    match distances.get(goal.0, goal.1) {
+        Some(&Some(_)) => Some(walk_back(&previous, goal)),
+        _ => None,
+    }
+}
+
+/// The path with the fewest moves through cells for which `passable` holds.
+pub fn bfs<T, M, F>(grid: &Slice<T, M>,
+                    start: (usize, usize),
+                    goal: (usize, usize),
+                    connectivity: Connectivity,
+                    passable: F)
+                    -> Option<Vec<(usize, usize)>>
+    where F: FnMut(&T) -> bool
+{
+    path(breadth_first(grid, start, Some(goal), connectivity, passable), goal)
+}
+
+/// The number of moves from `start` to every cell, or `None` for unreachable
+/// cells.
+pub fn bfs_distances<T, M, F>(grid: &Slice<T, M>,
+                              start: (usize, usize),
+                              connectivity: Connectivity,
+                              passable: F)
+                              -> VecVec<Option<u32>>
+    where F: FnMut(&T) -> bool
+{
+    breadth_first(grid, start, None, connectivity, passable).0
+}
+
+/// The cheapest path, where `cost` gives the cost of entering a cell or `None`
+/// if it cannot be entered.
+pub fn dijkstra<T, M, F>(grid: &Slice<T, M>,
+                         start: (usize, usize),
+                         goal: (usize, usize),
+                         connectivity: Connectivity,
+                         cost: F)
+                         -> Option<Vec<(usize, usize)>>
+    where F: FnMut(&T) -> Option<u32>
+{
+    path(best_first(grid, start, Some(goal), connectivity, cost, |_, _| 0),
+         goal)
+}
+
+/// The cost of the cheapest path from `start` to every cell, or `None` for
+/// unreachable cells.
+pub fn dijkstra_distances<T, M, F>(grid: &Slice<T, M>,
+                                   start: (usize, usize),
+                                   connectivity: Connectivity,
+                                   cost: F)
+                                   -> VecVec<Option<u32>>
+    where F: FnMut(&T) -> Option<u32>
+{
+    best_first(grid, start, None, connectivity, cost, |_, _| 0).0
+}
+
+/// Like `dijkstra`, but guided towards `goal` by the Manhattan (for
+/// `Connectivity::Four`) or Chebyshev (for `Connectivity::Eight`) distance.
+/// The path found is only guaranteed to be the cheapest if every cell that can
+/// be entered costs at least 1.
+pub fn astar<T, M, F>(grid: &Slice<T, M>,
+                      start: (usize, usize),
+                      goal: (usize, usize),
+                      connectivity: Connectivity,
+                      cost: F)
+                      -> Option<Vec<(usize, usize)>>
+    where F: FnMut(&T) -> Option<u32>
+{
+    let heuristic = |x: usize, y: usize| {
+        let (dx, dy) = (x.abs_diff(goal.0), y.abs_diff(goal.1));
+        (match connectivity {
+            Connectivity::Four => dx + dy,
+            Connectivity::Eight => dx.max(dy),
+        }) as u32
+    };
+    path(best_first(grid, start, Some(goal), connectivity, cost, heuristic),
+         goal)
+}
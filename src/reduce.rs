@@ -0,0 +1,153 @@
+use {ImmutableSlice, Num, Slice, VecVec};
+
+// The first cell in row-major order for which `better(cell, best)` holds against
+// every earlier cell. Cells unordered with themselves, like `NaN`, are skipped
+// unless there are no other cells.
+fn extreme<'a, T, F>(slice: ImmutableSlice<'a, T>, better: F) -> Option<((usize, usize), &'a T)>
+    where T: PartialOrd,
+          F: Fn(&T, &T) -> bool
+{
+    slice.cells()
+        .filter(|&(_, value)| value.partial_cmp(value).is_some())
+        .fold(None, |best, (position, value)| {
+            match best {
+                Some((_, best_value)) if !better(value, best_value) => best,
+                _ => Some((position, value)),
+            }
+        })
+        .or_else(|| slice.cells().next())
+}
+
+fn less<T: PartialOrd>(a: &T, b: &T) -> bool {
+    a < b
+}
+
+fn greater<T: PartialOrd>(a: &T, b: &T) -> bool {
+    a > b
+}
+
+impl<T: Num> VecVec<T> {
+    pub fn sum(&self) -> T {
+        self.as_slice().sum()
+    }
+
+    pub fn product(&self) -> T {
+        self.as_slice().product()
+    }
+
+    pub fn row_sums(&self) -> Vec<T> {
+        self.as_slice().row_sums()
+    }
+
+    pub fn column_sums(&self) -> Vec<T> {
+        self.as_slice().column_sums()
+    }
+}
+
+impl<T: PartialOrd> VecVec<T> {
+    pub fn min(&self) -> Option<&T> {
+        extreme(self.as_slice(), less).map(|(_, value)| value)
+    }
+
+    pub fn max(&self) -> Option<&T> {
+        extreme(self.as_slice(), greater).map(|(_, value)| value)
+    }
+
+    pub fn argmin(&self) -> Option<(usize, usize)> {
+        self.as_slice().argmin()
+    }
+
+    pub fn argmax(&self) -> Option<(usize, usize)> {
+        self.as_slice().argmax()
+    }
+
+    pub fn row_min(&self) -> Vec<T>
+        where T: Clone
+    {
+        self.as_slice().row_min()
+    }
+
+    pub fn row_max(&self) -> Vec<T>
+        where T: Clone
+    {
+        self.as_slice().row_max()
+    }
+
+    pub fn column_min(&self) -> Vec<T>
+        where T: Clone
+    {
+        self.as_slice().column_min()
+    }
+
+    pub fn column_max(&self) -> Vec<T>
+        where T: Clone
+    {
+        self.as_slice().column_max()
+    }
+}
+
+impl<T: Num, Mutability> Slice<T, Mutability> {
+    pub fn sum(&self) -> T {
+        self.as_immutable().cells().fold(T::zero(), |sum, (_, &value)| sum + value)
+    }
+
+    pub fn product(&self) -> T {
+        self.as_immutable().cells().fold(T::one(), |product, (_, &value)| product * value)
+    }
+
+    pub fn row_sums(&self) -> Vec<T> {
+        (0..self.height).map(|y| self.row(y).unwrap().sum()).collect()
+    }
+
+    pub fn column_sums(&self) -> Vec<T> {
+        (0..self.width).map(|x| self.column(x).unwrap().sum()).collect()
+    }
+}
+
+// `min` and `max` return the first of several equal extremes and ignore values
+// unordered with themselves, like `NaN`, unless every value is.
+impl<T: PartialOrd, Mutability> Slice<T, Mutability> {
+    pub fn min(&self) -> Option<&T> {
+        extreme(self.as_immutable(), less).map(|(_, value)| value)
+    }
+
+    pub fn max(&self) -> Option<&T> {
+        extreme(self.as_immutable(), greater).map(|(_, value)| value)
+    }
+
+    pub fn argmin(&self) -> Option<(usize, usize)> {
+        extreme(self.as_immutable(), less).map(|(position, _)| position)
+    }
+
+    pub fn argmax(&self) -> Option<(usize, usize)> {
+        extreme(self.as_immutable(), greater).map(|(position, _)| position)
+    }
+
+    /// The minimum of each row, or an empty `Vec` if the rows are empty.
+    pub fn row_min(&self) -> Vec<T>
+        where T: Clone
+    {
+        (0..self.height).filter_map(|y| self.row(y).unwrap().min().cloned()).collect()
+    }
+
+    /// The maximum of each row, or an empty `Vec` if the rows are empty.
+    pub fn row_max(&self) -> Vec<T>
+        where T: Clone
+    {
+        (0..self.height).filter_map(|y| self.row(y).unwrap().max().cloned()).collect()
+    }
+
+    /// The minimum of each column, or an empty `Vec` if the columns are empty.
+    pub fn column_min(&self) -> Vec<T>
+        where T: Clone
+    {
+        (0..self.width).filter_map(|x| self.column(x).unwrap().min().cloned()).collect()
+    }
+
+    /// The maximum of each column, or an empty `Vec` if the columns are empty.
+    pub fn column_max(&self) -> Vec<T>
+        where T: Clone
+    {
+        (0..self.width).filter_map(|x| self.column(x).unwrap().max().cloned()).collect()
+    }
+}
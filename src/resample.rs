@@ -0,0 +1,134 @@
+use {Slice, VecVec};
+
+/// Values that can be blended for resampling.
+pub trait Interpolate: Sized {
+    /// The sum of `values` scaled by their weights, which sum to 1.
+    fn blend(values: &[(&Self, f64)]) -> Self;
+}
+
+macro_rules! interpolate_float {
+    ($($ty:ty),*) => {
+        $(
+            impl Interpolate for $ty {
+                fn blend(values: &[(&Self, f64)]) -> Self {
+                    values.iter().map(|&(&value, weight)| value as f64 * weight).sum::<f64>() as $ty
+                }
+            }
+        )*
+    }
+}
+
+macro_rules! interpolate_int {
+    ($($ty:ty),*) => {
+        $(
+            impl Interpolate for $ty {
+                fn blend(values: &[(&Self, f64)]) -> Self {
+                    let sum = values.iter().map(|&(&value, weight)| value as f64 * weight).sum::<f64>();
+                    sum.round() as $ty
+                }
+            }
+        )*
+    }
+}
+
+interpolate_float!(f32, f64);
+interpolate_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+// Blends each channel of color-like arrays such as `[u8; 3]` separately.
+impl<T: Interpolate, const N: usize> Interpolate for [T; N] {
+    fn blend(values: &[(&Self, f64)]) -> Self {
+        ::std::array::from_fn(|i| {
+            let channel = values.iter().map(|&(value, weight)| (&value[i], weight));
+            T::blend(&channel.collect::<Vec<_>>())
+        })
+    }
+}
+
+impl<T> VecVec<T> {
+    pub fn resize_nearest(&self, width: usize, height: usize) -> Option<VecVec<T>>
+        where T: Clone
+    {
+        self.as_slice().resize_nearest(width, height)
+    }
+
+    pub fn resize_bilinear(&self, width: usize, height: usize) -> Option<VecVec<T>>
+        where T: Interpolate
+    {
+        self.as_slice().resize_bilinear(width, height)
+    }
+
+    pub fn downsample_area(&self, factor_x: usize, factor_y: usize) -> Option<VecVec<T>>
+        where T: Interpolate
+    {
+        self.as_slice().downsample_area(factor_x, factor_y)
+    }
+}
+
+impl<T, Mutability> Slice<T, Mutability> {
+    fn can_resize(&self, width: usize, height: usize) -> bool {
+        (self.width != 0 && self.height != 0) || width == 0 || height == 0
+    }
+
+    /// Resizes to `width x height` by copying the nearest cell. Returns `None`
+    /// if the slice is empty but the requested size is not.
+    pub fn resize_nearest(&self, width: usize, height: usize) -> Option<VecVec<T>>
+        where T: Clone
+    {
+        if !self.can_resize(width, height) {
+            return None;
+        }
+        let nearest = |i: usize, from: usize, to: usize| ((2 * i + 1) * from / (2 * to)).min(from - 1);
+        Some(VecVec::from_fn(width, height, |x, y| {
+            self.get(nearest(x, self.width, width), nearest(y, self.height, height))
+                .unwrap()
+                .clone()
+        }))
+    }
+
+    /// Resizes to `width x height` by bilinear interpolation between cell
+    /// centers. Returns `None` if the slice is empty but the requested size is
+    /// not.
+    pub fn resize_bilinear(&self, width: usize, height: usize) -> Option<VecVec<T>>
+        where T: Interpolate
+    {
+        if !self.can_resize(width, height) {
+            return None;
+        }
+        // The two source indices around target index `i` and the weight of the
+        // second.
+        let sample = |i: usize, from: usize, to: usize| {
+            let position = ((i as f64 + 0.5) * from as f64 / to as f64 - 0.5)
+                .max(0.0)
+                .min((from - 1) as f64);
+            let low = position.floor() as usize;
+            ((low, (low + 1).min(from - 1)), position - low as f64)
+        };
+        Some(VecVec::from_fn(width, height, |x, y| {
+            let ((x0, x1), tx) = sample(x, self.width, width);
+            let ((y0, y1), ty) = sample(y, self.height, height);
+            T::blend(&[(self.get(x0, y0).unwrap(), (1.0 - tx) * (1.0 - ty)),
+                       (self.get(x1, y0).unwrap(), tx * (1.0 - ty)),
+                       (self.get(x0, y1).unwrap(), (1.0 - tx) * ty),
+                       (self.get(x1, y1).unwrap(), tx * ty)])
+        }))
+    }
+
+    /// Shrinks by averaging each `factor_x x factor_y` block into one cell.
+    /// Cells that do not fill a whole block at the right and bottom edges are
+    /// dropped. Returns `None` if either factor is 0.
+    pub fn downsample_area(&self, factor_x: usize, factor_y: usize) -> Option<VecVec<T>>
+        where T: Interpolate
+    {
+        if factor_x == 0 || factor_y == 0 {
+            return None;
+        }
+        let weight = 1.0 / (factor_x * factor_y) as f64;
+        Some(VecVec::from_fn(self.width / factor_x, self.height / factor_y, |x, y| {
+            let block = (0..factor_y)
+                .flat_map(|dy| (0..factor_x).map(move |dx| (dx, dy)))
+                .map(|(dx, dy)| (self.get(x * factor_x + dx, y * factor_y + dy).unwrap(), weight))
+                .collect::<Vec<_>>();
+            T::blend(&block)
+        }))
+    }
+}
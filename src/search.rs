@@ -0,0 +1,106 @@
+use {ImmutableSlice, Slice, VecVec};
+
+fn positions<'a, T, F>(slice: ImmutableSlice<'a, T>,
+                       mut predicate: F)
+                       -> impl Iterator<Item = (usize, usize)> + 'a
+    where F: FnMut(&T) -> bool + 'a
+{
+    slice.cells().filter(move |&(_, value)| predicate(value)).map(|(position, _)| position)
+}
+
+impl<T> VecVec<T> {
+    pub fn position<F>(&self, predicate: F) -> Option<(usize, usize)>
+        where F: FnMut(&T) -> bool
+    {
+        self.as_slice().position(predicate)
+    }
+
+    pub fn rposition<F>(&self, predicate: F) -> Option<(usize, usize)>
+        where F: FnMut(&T) -> bool
+    {
+        self.as_slice().rposition(predicate)
+    }
+
+    pub fn positions<'a, F>(&'a self, predicate: F) -> impl Iterator<Item = (usize, usize)> + 'a
+        where F: FnMut(&T) -> bool + 'a
+    {
+        positions(self.as_slice(), predicate)
+    }
+
+    pub fn contains(&self, value: &T) -> bool
+        where T: PartialEq
+    {
+        self.as_slice().contains(value)
+    }
+
+    pub fn count<F>(&self, predicate: F) -> usize
+        where F: FnMut(&T) -> bool
+    {
+        self.as_slice().count(predicate)
+    }
+
+    pub fn find_subgrid<M>(&self, needle: &Slice<T, M>) -> Option<(usize, usize)>
+        where T: PartialEq
+    {
+        self.as_slice().find_subgrid(needle)
+    }
+}
+
+impl<T, Mutability> Slice<T, Mutability> {
+    /// The first position in row-major order whose value matches `predicate`.
+    pub fn position<F>(&self, predicate: F) -> Option<(usize, usize)>
+        where F: FnMut(&T) -> bool
+    {
+        self.positions(predicate).next()
+    }
+
+    /// The last position in row-major order whose value matches `predicate`.
+    pub fn rposition<F>(&self, mut predicate: F) -> Option<(usize, usize)>
+        where F: FnMut(&T) -> bool
+    {
+        for y in (0..self.height).rev() {
+            for x in (0..self.width).rev() {
+                if predicate(self.get(x, y).unwrap()) {
+                    return Some((x, y));
+                }
+            }
+        }
+        None
+    }
+
+    /// All positions in row-major order whose values match `predicate`.
+    pub fn positions<'a, F>(&'a self, predicate: F) -> impl Iterator<Item = (usize, usize)> + 'a
+        where F: FnMut(&T) -> bool + 'a
+    {
+        positions(self.as_immutable(), predicate)
+    }
+
+    pub fn contains(&self, value: &T) -> bool
+        where T: PartialEq
+    {
+        self.position(|cell| cell == value).is_some()
+    }
+
+    pub fn count<F>(&self, predicate: F) -> usize
+        where F: FnMut(&T) -> bool
+    {
+        self.positions(predicate).count()
+    }
+
+    /// The top-left position of the first occurrence of `needle` in row-major
+    /// order.
+    pub fn find_subgrid<M>(&self, needle: &Slice<T, M>) -> Option<(usize, usize)>
+        where T: PartialEq
+    {
+        if needle.width > self.width || needle.height > self.height {
+            return None;
+        }
+        if needle.width == 0 || needle.height == 0 {
+            return Some((0, 0));
+        }
+        self.as_immutable()
+            .windows(needle.width, needle.height)
+            .find(|window| window == needle)
+            .map(|window| (window.x - self.x, window.y - self.y))
+    }
+}
@@ -0,0 +1,36 @@
+//! Serializes a `VecVec` as a sequence of rows instead of `{width, height,
+//! data}`, for use with `#[serde(with = "vecvec::serde_rows")]`.
+//!
+//! Rows of different lengths are rejected when deserializing.
+
+use serde::de::{self, Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+
+use VecVec;
+
+pub fn serialize<T, S>(vv: &VecVec<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where T: Serialize,
+          S: Serializer
+{
+    serializer.collect_seq((0..vv.height).map(|y| &vv.inner[y * vv.width..(y + 1) * vv.width]))
+}
+
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<VecVec<T>, D::Error>
+    where T: Deserialize<'de>,
+          D: Deserializer<'de>
+{
+    let rows = Vec::<Vec<T>>::deserialize(deserializer)?;
+    let (width, height) = (rows.first().map_or(0, |row| row.len()), rows.len());
+    if let Some((y, row)) = rows.iter().enumerate().find(|&(_, row)| row.len() != width) {
+        return Err(de::Error::custom(format_args!("expected row {} to have {} elements like the \
+                                                   first row, found {}",
+                                                  y,
+                                                  width,
+                                                  row.len())));
+    }
+    Ok(VecVec {
+        inner: rows.into_iter().flatten().collect(),
+        width,
+        height,
+    })
+}
@@ -0,0 +1,58 @@
+use serde::de::{self, Deserialize, Deserializer};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+use {Slice, VecVec};
+
+// Grids and slices serialize as `{width, height, data}` with `data` in
+// row-major order.
+
+impl<T: Serialize> Serialize for VecVec<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_slice().serialize(serializer)
+    }
+}
+
+impl<T: Serialize, Mutability> Serialize for Slice<T, Mutability> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        struct Data<'a, T: 'a, Mutability: 'a>(&'a Slice<T, Mutability>);
+
+        impl<'a, T: Serialize, Mutability> Serialize for Data<'a, T, Mutability> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_seq(self.0.as_immutable().cells().map(|(_, value)| value))
+            }
+        }
+
+        let mut state = serializer.serialize_struct("VecVec", 3)?;
+        state.serialize_field("width", &self.width)?;
+        state.serialize_field("height", &self.height)?;
+        state.serialize_field("data", &Data(self))?;
+        state.end()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "VecVec")]
+struct Raw<T> {
+    width: usize,
+    height: usize,
+    data: Vec<T>,
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for VecVec<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let Raw { width, height, data } = Raw::deserialize(deserializer)?;
+        if width.checked_mul(height) != Some(data.len()) {
+            return Err(de::Error::custom(format_args!("expected {} elements for a {}x{} VecVec, \
+                                                       found {}",
+                                                      width as u128 * height as u128,
+                                                      width,
+                                                      height,
+                                                      data.len())));
+        }
+        Ok(VecVec {
+            inner: data,
+            width,
+            height,
+        })
+    }
+}
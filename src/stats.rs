@@ -0,0 +1,126 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use {Num, Slice, VecVec};
+
+// Only called on values that are ordered with themselves, which makes this a
+// total order.
+fn compare<T: PartialOrd>(a: &T, b: &T) -> Ordering {
+    a.partial_cmp(b).unwrap_or(Ordering::Equal)
+}
+
+impl<T: Num> VecVec<T> {
+    pub fn histogram(&self, bins: usize, range: (f64, f64)) -> Vec<usize> {
+        self.as_slice().histogram(bins, range)
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        self.as_slice().mean()
+    }
+
+    pub fn variance(&self) -> Option<f64> {
+        self.as_slice().variance()
+    }
+
+    pub fn median(&self) -> Option<f64> {
+        self.as_slice().median()
+    }
+
+    pub fn percentile(&self, p: f64) -> Option<f64> {
+        self.as_slice().percentile(p)
+    }
+}
+
+impl<T: Clone + Eq + Hash> VecVec<T> {
+    pub fn value_counts(&self) -> HashMap<T, usize> {
+        self.as_slice().value_counts()
+    }
+}
+
+impl<T: Num, Mutability> Slice<T, Mutability> {
+    /// Counts the values falling into each of `bins` equal-width bins spanning
+    /// `range`. The last bin includes the upper end of the range; values
+    /// outside the range and `NaN`s are not counted.
+    pub fn histogram(&self, bins: usize, (low, high): (f64, f64)) -> Vec<usize> {
+        let mut histogram = vec![0; bins];
+        if bins == 0 || low.is_nan() || high.is_nan() || low > high {
+            return histogram;
+        }
+        for (_, value) in self.as_immutable().cells() {
+            let value = value.to_f64();
+            if !(low..=high).contains(&value) {
+                continue;
+            }
+            let bin = if high == low {
+                0
+            } else {
+                (((value - low) / (high - low) * bins as f64) as usize).min(bins - 1)
+            };
+            histogram[bin] += 1;
+        }
+        histogram
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        if self.width == 0 || self.height == 0 {
+            return None;
+        }
+        let sum = self.as_immutable().cells().map(|(_, value)| value.to_f64()).sum::<f64>();
+        Some(sum / (self.width * self.height) as f64)
+    }
+
+    /// The population variance.
+    pub fn variance(&self) -> Option<f64> {
+        let mean = self.mean()?;
+        let squares = self.as_immutable()
+            .cells()
+            .map(|(_, value)| (value.to_f64() - mean).powi(2))
+            .sum::<f64>();
+        Some(squares / (self.width * self.height) as f64)
+    }
+
+    /// The median, averaging the two middle values if there is an even number
+    /// of them. `NaN`s are ignored.
+    pub fn median(&self) -> Option<f64> {
+        self.percentile(50.0)
+    }
+
+    /// The `p`th percentile for `p` between 0 and 100, interpolating linearly
+    /// between the closest ranks. `NaN`s are ignored, and `None` is returned if
+    /// no other values are left. Uses selection rather than sorting, so runs in
+    /// linear time on average.
+    pub fn percentile(&self, p: f64) -> Option<f64> {
+        // Selection reorders the values, so this is the one statistic that
+        // copies them out. Values unordered with themselves are `NaN`s, which
+        // have no rank.
+        let mut values = self.as_immutable()
+            .cells()
+            .map(|(_, &value)| value)
+            .filter(|value| value.partial_cmp(value).is_some())
+            .collect::<Vec<_>>();
+        if values.is_empty() || !(0.0..=100.0).contains(&p) {
+            return None;
+        }
+        let rank = p / 100.0 * (values.len() - 1) as f64;
+        let index = rank.floor() as usize;
+        let (_, &mut low, above) = values.select_nth_unstable_by(index, compare);
+        let fraction = rank - index as f64;
+        if fraction == 0.0 {
+            return Some(low.to_f64());
+        }
+        let high = *above.iter().min_by(|a, b| compare(*a, *b)).unwrap();
+        Some(low.to_f64() + (high.to_f64() - low.to_f64()) * fraction)
+    }
+}
+
+impl<T: Clone + Eq + Hash, Mutability> Slice<T, Mutability> {
+    /// The number of cells holding each distinct value.
+    pub fn value_counts(&self) -> HashMap<T, usize> {
+        let mut counts = HashMap::new();
+        for (_, value) in self.as_immutable().cells() {
+            *counts.entry(value.clone()).or_insert(0) += 1;
+        }
+        counts
+    }
+}
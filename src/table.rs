@@ -0,0 +1,75 @@
+use std::fmt;
+
+use Slice;
+
+// Like numpy, grids with more cells than `THRESHOLD` only show the first and
+// last `EDGE_ITEMS` rows and columns.
+const THRESHOLD: usize = 1000;
+const EDGE_ITEMS: usize = 3;
+
+// The indices shown along an axis of length `len`, with `None` in place of the
+// elided middle.
+fn indices(len: usize, elide: bool) -> Vec<Option<usize>> {
+    if elide && len > 2 * EDGE_ITEMS {
+        (0..EDGE_ITEMS)
+            .map(Some)
+            .chain(Some(None))
+            .chain((len - EDGE_ITEMS..len).map(Some))
+            .collect()
+    } else {
+        (0..len).map(Some).collect()
+    }
+}
+
+fn label(index: Option<usize>) -> String {
+    index.map_or_else(|| "...".to_string(), |index| index.to_string())
+}
+
+// Writes `slice` as a table with right-aligned columns and row and column
+// indices. `cell` formats a cell with the precision of `f`; the width of `f`
+// is the minimum width of each column.
+pub(crate) fn table<T, M, F>(slice: &Slice<T, M>,
+                             f: &mut fmt::Formatter,
+                             mut cell: F)
+                             -> fmt::Result
+    where F: FnMut(&T, Option<usize>) -> String
+{
+    let elide = slice.width().saturating_mul(slice.height()) > THRESHOLD;
+    let xs = indices(slice.width(), elide);
+    let ys = indices(slice.height(), elide);
+
+    let mut rows = vec![xs.iter().map(|&x| label(x)).collect::<Vec<_>>()];
+    for &y in &ys {
+        rows.push(xs.iter()
+            .map(|&x| match (x, y) {
+                (Some(x), Some(y)) => cell(slice.get(x, y).unwrap(), f.precision()),
+                (None, Some(_)) => "...".to_string(),
+                (_, None) => String::new(),
+            })
+            .collect());
+    }
+
+    let mut widths = vec![f.width().unwrap_or(0); xs.len()];
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let labels = Some(String::new())
+        .into_iter()
+        .chain(ys.iter().map(|&y| label(y)))
+        .collect::<Vec<_>>();
+    let label_width = labels.iter().map(|label| label.len()).max().unwrap_or(0);
+
+    for (i, (label, row)) in labels.iter().zip(&rows).enumerate() {
+        let mut line = format!("{:>1$}", label, label_width);
+        for (cell, &width) in row.iter().zip(&widths) {
+            line.push_str(&format!(" {:>1$}", cell, width));
+        }
+        if i > 0 {
+            f.write_str("\n")?;
+        }
+        f.write_str(line.trim_end())?;
+    }
+    Ok(())
+}
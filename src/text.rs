@@ -0,0 +1,113 @@
+use std::fmt;
+
+use {table, ImmutableSlice, Slice, VecVec};
+
+/// Renders a grid as text with one row per line, created by `text`. Cells are
+/// written with their `Display` impl, so width and precision flags apply to
+/// each cell.
+pub struct Text<'a, T: 'a> {
+    slice: ImmutableSlice<'a, T>,
+    separator: &'a str,
+    row_separator: &'a str,
+}
+
+impl<'a, T> Text<'a, T> {
+    /// Sets the string written between cells of a row. Defaults to `" "`.
+    pub fn separator(mut self, separator: &'a str) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    /// Sets the string written between rows. Defaults to `"\n"`.
+    pub fn row_separator(mut self, row_separator: &'a str) -> Self {
+        self.row_separator = row_separator;
+        self
+    }
+}
+
+impl<'a, T: fmt::Display> fmt::Display for Text<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for y in 0..self.slice.height {
+            if y > 0 {
+                f.write_str(self.row_separator)?;
+            }
+            for x in 0..self.slice.width {
+                if x > 0 {
+                    f.write_str(self.separator)?;
+                }
+                self.slice.get(x, y).unwrap().fmt(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn text<T>(slice: ImmutableSlice<'_, T>) -> Text<'_, T> {
+    Text {
+        slice,
+        separator: " ",
+        row_separator: "\n",
+    }
+}
+
+impl VecVec<char> {
+    /// Parses a grid with one row per line, like `"#..#\n.##."`. Returns `None`
+    /// if the lines have different lengths.
+    pub fn parse_text(text: &str) -> Option<Self> {
+        VecVec::parse_text_with(text, |c| c)
+    }
+}
+
+impl<T> VecVec<T> {
+    /// Like `parse_text`, but maps each character with `f`.
+    pub fn parse_text_with<F>(text: &str, mut f: F) -> Option<Self>
+        where F: FnMut(char) -> T
+    {
+        let mut inner = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+        for line in text.lines() {
+            let start = inner.len();
+            inner.extend(line.chars().map(&mut f));
+            if *width.get_or_insert(inner.len() - start) != inner.len() - start {
+                return None;
+            }
+            height += 1;
+        }
+        Some(VecVec {
+            inner,
+            width: width.unwrap_or(0),
+            height,
+        })
+    }
+
+    pub fn text(&self) -> Text<'_, T> {
+        text(self.as_slice())
+    }
+}
+
+impl<T, Mutability> Slice<T, Mutability> {
+    pub fn text(&self) -> Text<'_, T> {
+        text(self.as_immutable())
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for VecVec<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.as_slice(), f)
+    }
+}
+
+/// With the alternate flag, `{:#}` renders an aligned table with row and column
+/// indices instead, eliding the middle of large grids like numpy.
+impl<T: fmt::Display, Mutability> fmt::Display for Slice<T, Mutability> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            return table::table(self, f, |cell, precision| match precision {
+                Some(precision) => format!("{:.*}", precision, cell),
+                None => cell.to_string(),
+            });
+        }
+        self.text().fmt(f)
+    }
+}
@@ -0,0 +1,74 @@
+use {Immutable, ImmutableSlice, Slice, VecVec};
+
+/// Iterator over overlapping `width x height` windows of a slice in row-major
+/// order, created by `windows` and `windows_step`.
+pub struct Windows<'a, T: 'a> {
+    slice: ImmutableSlice<'a, T>,
+    width: usize,
+    height: usize,
+    step_x: usize,
+    step_y: usize,
+    x: usize,
+    y: usize,
+}
+
+impl<'a, T> Iterator for Windows<'a, T> {
+    type Item = ImmutableSlice<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.width > self.slice.width || self.y.saturating_add(self.height) > self.slice.height {
+            return None;
+        }
+        let window = self.slice.slice(self.x, self.y, self.width, self.height).unwrap();
+        // Steps past the end saturate, which ends the row or the iteration.
+        self.x = self.x.saturating_add(self.step_x);
+        if self.x.saturating_add(self.width) > self.slice.width {
+            self.x = 0;
+            self.y = self.y.saturating_add(self.step_y);
+        }
+        Some(window)
+    }
+}
+
+impl<T> VecVec<T> {
+    pub fn windows(&self, width: usize, height: usize) -> Windows<'_, T> {
+        self.windows_step(width, height, 1, 1)
+    }
+
+    pub fn windows_step(&self,
+                        width: usize,
+                        height: usize,
+                        step_x: usize,
+                        step_y: usize)
+                        -> Windows<'_, T> {
+        self.as_slice().windows_step(width, height, step_x, step_y)
+    }
+}
+
+impl<'a, T> Slice<T, Immutable<'a, T>> {
+    /// Panics if `width` or `height` is 0.
+    pub fn windows(&self, width: usize, height: usize) -> Windows<'a, T> {
+        self.windows_step(width, height, 1, 1)
+    }
+
+    /// Like `windows`, but advances `step_x` columns and `step_y` rows between
+    /// windows. Panics if any argument is 0.
+    pub fn windows_step(&self,
+                        width: usize,
+                        height: usize,
+                        step_x: usize,
+                        step_y: usize)
+                        -> Windows<'a, T> {
+        assert!(width != 0 && height != 0, "window size must be non-zero");
+        assert!(step_x != 0 && step_y != 0, "window step must be non-zero");
+        Windows {
+            slice: self.slice(0, 0, self.width, self.height).unwrap(),
+            width,
+            height,
+            step_x,
+            step_y,
+            x: 0,
+            y: 0,
+        }
+    }
+}
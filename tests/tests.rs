@@ -1,3 +1,11 @@
+#![allow(clippy::just_underscores_and_digits)]
+
+#[cfg(feature = "image")]
+extern crate image;
+#[cfg(feature = "nalgebra")]
+extern crate nalgebra;
+#[cfg(feature = "ndarray")]
+extern crate ndarray;
 extern crate vecvec;
 
 use vecvec::VecVec;
@@ -325,3 +333,1190 @@ fn it_works() {
     assert_eq!(vv.hsplit_at_mut(0).unwrap().0.vsplit_at_mut(5), None);
     assert_eq!(vv.vsplit_at_mut(5), None);
 }
+
+#[test]
+fn neighbors() {
+    use vecvec::{Boundary, Stencil};
+
+    let mut vv = VecVec::new(3, 3, 0);
+    for (i, (x, y)) in (0..3).flat_map(|y| (0..3).map(move |x| (x, y))).enumerate() {
+        *vv.get_mut(x, y).unwrap() = i;
+    }
+
+    let n4 = vv.neighbors4(1, 1).map(|(p, &v)| (p, v)).collect::<Vec<_>>();
+    assert_eq!(n4, [((1, 0), 1), ((0, 1), 3), ((2, 1), 5), ((1, 2), 7)]);
+    let n4 = vv.neighbors4(0, 0).map(|(p, &v)| (p, v)).collect::<Vec<_>>();
+    assert_eq!(n4, [((1, 0), 1), ((0, 1), 3)]);
+    assert_eq!(vv.neighbors8(1, 1).count(), 8);
+    assert_eq!(vv.neighbors8(2, 2).map(|(_, &v)| v).collect::<Vec<_>>(), [4, 5, 7]);
+    assert_eq!(vv.neighbors4(usize::MAX, 0).count(), 0);
+    assert_eq!(vv.neighbors8(0, usize::MAX).count(), 0);
+
+    let slice = vv.slice(1, 1, 2, 2).unwrap();
+    let n8 = slice.neighbors8(0, 0).map(|(p, &v)| (p, v)).collect::<Vec<_>>();
+    assert_eq!(n8, [((1, 0), 5), ((0, 1), 7), ((1, 1), 8)]);
+
+    let four = Stencil::four();
+    let values = |boundary: &Boundary<usize>| {
+        slice.neighbors(0, 0, &four, boundary).map(|(p, &v)| (p, v)).collect::<Vec<_>>()
+    };
+    assert_eq!(values(&Boundary::Skip), [((1, 0), 5), ((0, 1), 7)]);
+    assert_eq!(values(&Boundary::Clamp),
+               [((0, 0), 4), ((0, 0), 4), ((1, 0), 5), ((0, 1), 7)]);
+    assert_eq!(values(&Boundary::Wrap),
+               [((0, 1), 7), ((1, 0), 5), ((1, 0), 5), ((0, 1), 7)]);
+    assert_eq!(values(&Boundary::Constant(100)),
+               [((0, -1), 100), ((-1, 0), 100), ((1, 0), 5), ((0, 1), 7)]);
+
+    let knight = Stencil::new(vec![(1, 2), (2, 1), (-1, 2)]);
+    let n = vv.neighbors(0, 0, &knight, &Boundary::Skip).map(|(p, &v)| (p, v)).collect::<Vec<_>>();
+    assert_eq!(n, [((1, 2), 7), ((2, 1), 5)]);
+
+    let far = Stencil::new(vec![(isize::MAX, 0), (0, isize::MIN)]);
+    let n = vv.neighbors(1, 1, &far, &Boundary::Wrap).map(|(p, &v)| (p, v)).collect::<Vec<_>>();
+    assert_eq!(n, [((2, 1), 5), ((1, 2), 7)]);
+    let n = vv.neighbors(1, 1, &far, &Boundary::Clamp).map(|(p, &v)| (p, v)).collect::<Vec<_>>();
+    assert_eq!(n, [((2, 1), 5), ((1, 0), 1)]);
+    assert_eq!(vv.neighbors(1, 1, &far, &Boundary::Skip).count(), 0);
+    let n = vv.neighbors(1, 1, &far, &Boundary::Constant(100)).map(|(p, _)| p).collect::<Vec<_>>();
+    assert_eq!(n, [(isize::MAX, 1), (1, isize::MIN + 1)]);
+
+    let wide = VecVec::new(usize::MAX, 1, ());
+    let right = Stencil::new(vec![(1, 0)]);
+    let n = wide.neighbors(usize::MAX - 1, 0, &right, &Boundary::Clamp).map(|(p, _)| p);
+    assert_eq!(n.collect::<Vec<_>>(), [(isize::MAX, 0)]);
+}
+
+#[test]
+fn get_with_boundary() {
+    use vecvec::Boundary;
+
+    let mut vv = VecVec::new(4, 2, ' ');
+    for (x, c) in "abcd".chars().enumerate() {
+        *vv.get_mut(x, 0).unwrap() = c;
+        *vv.get_mut(x, 1).unwrap() = c.to_ascii_uppercase();
+    }
+
+    let row = |boundary: &Boundary<char>| {
+        (-3..7)
+            .map(|x| vv.get_with_boundary(x, 0, boundary).cloned().unwrap_or('_'))
+            .collect::<String>()
+    };
+    assert_eq!(row(&Boundary::Skip), "___abcd___");
+    assert_eq!(row(&Boundary::Clamp), "aaaabcdddd");
+    assert_eq!(row(&Boundary::Wrap), "bcdabcdabc");
+    assert_eq!(row(&Boundary::Mirror), "cbaabcddcb");
+    assert_eq!(row(&Boundary::Reflect101), "dcbabcdcba");
+    assert_eq!(row(&Boundary::Constant('x')), "xxxabcdxxx");
+
+    assert_eq!(vv.get_with_boundary(0, -1, &Boundary::Mirror), Some(&'a'));
+    assert_eq!(vv.get_with_boundary(0, -1, &Boundary::Reflect101), Some(&'A'));
+    assert_eq!(vv.get_with_boundary(-1, 2, &Boundary::Wrap), Some(&'d'));
+
+    let slice = vv.slice(1, 0, 2, 2).unwrap();
+    assert_eq!(slice.get_with_boundary(-1, 0, &Boundary::Clamp), Some(&'b'));
+    assert_eq!(slice.get_with_boundary(2, 1, &Boundary::Reflect101), Some(&'B'));
+    assert_eq!(slice.get_with_boundary(5, 0, &Boundary::Skip), None);
+
+    let single = vv.slice(0, 0, 1, 1).unwrap();
+    assert_eq!(single.get_with_boundary(-5, 3, &Boundary::Reflect101), Some(&'a'));
+    let empty = vv.slice(0, 0, 0, 0).unwrap();
+    assert_eq!(empty.get_with_boundary(0, 0, &Boundary::Clamp), None);
+    assert_eq!(empty.get_with_boundary(0, 0, &Boundary::Constant('x')), Some(&'x'));
+}
+
+#[test]
+fn windows() {
+    let mut vv = VecVec::new(4, 3, 0);
+    for (i, (x, y)) in (0..3).flat_map(|y| (0..4).map(move |x| (x, y))).enumerate() {
+        *vv.get_mut(x, y).unwrap() = i;
+    }
+
+    let windows = vv.windows(3, 2).collect::<Vec<_>>();
+    assert_eq!(windows.len(), 4);
+    assert_eq!(windows[0], s![s![0, 1, 2], s![4, 5, 6]]);
+    assert_eq!(windows[1], s![s![1, 2, 3], s![5, 6, 7]]);
+    assert_eq!(windows[2], s![s![4, 5, 6], s![8, 9, 10]]);
+    assert_eq!(windows[3], s![s![5, 6, 7], s![9, 10, 11]]);
+    assert_eq!((windows[3].x(), windows[3].y()), (1, 1));
+
+    assert_eq!(vv.windows(1, 1).count(), 12);
+    assert_eq!(vv.windows(4, 3).count(), 1);
+    assert_eq!(VecVec::new(2, 2, 0).windows_step(1, 1, usize::MAX, 1).count(), 2);
+    assert_eq!(VecVec::new(2, 2, 0).windows_step(1, 1, 1, usize::MAX).count(), 2);
+    assert_eq!(vv.windows(5, 1).count(), 0);
+    assert_eq!(vv.windows(1, 4).count(), 0);
+
+    let pooled = vv.windows_step(2, 2, 2, 2).collect::<Vec<_>>();
+    assert_eq!(pooled.len(), 2);
+    assert_eq!(pooled[0], s![s![0, 1], s![4, 5]]);
+    assert_eq!(pooled[1], s![s![2, 3], s![6, 7]]);
+
+    let slice = vv.slice(1, 1, 3, 2).unwrap();
+    let windows = slice.windows(2, 2).collect::<Vec<_>>();
+    assert_eq!(windows.len(), 2);
+    assert_eq!(windows[1], s![s![6, 7], s![10, 11]]);
+    assert_eq!((windows[1].x(), windows[1].y()), (2, 1));
+    assert_eq!(slice.windows_step(1, 1, 2, 1).count(), 4);
+}
+
+#[test]
+fn from_fn() {
+    let vv = VecVec::from_fn(3, 2, |x, y| x * 10 + y);
+    assert_eq!(vv.width(), 3);
+    assert_eq!(vv.height(), 2);
+    assert_eq!(vv.as_slice(), s![s![0, 10, 20], s![1, 11, 21]]);
+}
+
+#[test]
+fn conv() {
+    use vecvec::conv;
+    use vecvec::Boundary;
+
+    let input = VecVec::from_fn(4, 3, |x, y| (y * 4 + x) as i32);
+    let input = input.as_slice();
+
+    let identity = VecVec::from_fn(3, 3, |x, y| if (x, y) == (1, 1) { 1 } else { 0 });
+    assert!(conv::convolve2d(&input, &identity.as_slice(), &Boundary::Skip).as_slice() == input);
+
+    let shift = VecVec::from_fn(3, 1, |x, _| if x == 0 { 1 } else { 0 });
+    let correlated = conv::correlate2d(&input, &shift.as_slice(), &Boundary::Constant(-1));
+    assert_eq!(correlated.as_slice(),
+               s![s![-1, 0, 1, 2], s![-1, 4, 5, 6], s![-1, 8, 9, 10]]);
+    let convolved = conv::convolve2d(&input, &shift.as_slice(), &Boundary::Clamp);
+    assert_eq!(convolved.as_slice(),
+               s![s![1, 2, 3, 3], s![5, 6, 7, 7], s![9, 10, 11, 11]]);
+
+    let sobel_x = conv::sobel_x::<i32>();
+    assert_eq!(sobel_x.as_slice(), s![s![-1, 0, 1], s![-2, 0, 2], s![-1, 0, 1]]);
+    let gradient = conv::correlate2d(&input, &sobel_x.as_slice(), &Boundary::Clamp);
+    assert_eq!(gradient.as_slice(),
+               s![s![4, 8, 8, 4], s![4, 8, 8, 4], s![4, 8, 8, 4]]);
+    let gradient = conv::correlate2d(&input, &conv::sobel_y::<i32>().as_slice(), &Boundary::Clamp);
+    assert_eq!(*gradient.get(1, 1).unwrap(), 32);
+    let laplacian = conv::convolve2d(&input, &conv::laplacian::<i32>().as_slice(), &Boundary::Reflect101);
+    assert_eq!(laplacian.as_slice(),
+               s![s![10, 8, 8, 6], s![2, 0, 0, -2], s![-6, -8, -8, -10]]);
+
+    for boundary in &[Boundary::Skip,
+                      Boundary::Clamp,
+                      Boundary::Wrap,
+                      Boundary::Mirror,
+                      Boundary::Reflect101,
+                      Boundary::Constant(7)] {
+        let full = conv::convolve2d(&input, &sobel_x.as_slice(), boundary);
+        let separable = conv::convolve_separable(&input, &[-1, 0, 1], &[1, 2, 1], boundary);
+        assert_eq!(full, separable);
+    }
+
+    let box3 = conv::box_kernel::<f64>(3, 3);
+    assert!((*box3.get(2, 2).unwrap() - 1.0 / 9.0).abs() < 1e-12);
+    let gaussian = conv::gaussian_kernel::<f64>(5, 1.0);
+    let total = (0..5).flat_map(|y| (0..5).map(move |x| (x, y)))
+        .map(|(x, y)| *gaussian.get(x, y).unwrap())
+        .sum::<f64>();
+    assert!((total - 1.0).abs() < 1e-12);
+    assert!(gaussian.get(2, 2).unwrap() > gaussian.get(1, 2).unwrap());
+    assert_eq!(conv::gaussian_kernel_1d::<f32>(3, 1.0)[0], conv::gaussian_kernel_1d::<f32>(3, 1.0)[2]);
+
+    let flat = VecVec::new(5, 5, 2.0f64);
+    let blurred = conv::convolve2d(&flat.as_slice(), &gaussian.as_slice(), &Boundary::Clamp);
+    assert!((*blurred.get(0, 0).unwrap() - 2.0).abs() < 1e-12);
+}
+
+#[test]
+fn integral() {
+    use vecvec::Rect;
+
+    let mut vv = VecVec::from_fn(4, 3, |x, y| (y * 4 + x) as i64);
+    let naive = |vv: &VecVec<i64>, rect: Rect| {
+        (rect.y..rect.y + rect.height)
+            .flat_map(|y| (rect.x..rect.x + rect.width).map(move |x| (x, y)))
+            .map(|(x, y)| *vv.get(x, y).unwrap())
+            .sum::<i64>()
+    };
+
+    let mut integral = vv.integral::<i64>();
+    assert_eq!(integral.width(), 4);
+    assert_eq!(integral.height(), 3);
+    for rect in vv.windows(1, 1).chain(vv.windows(2, 2)).chain(vv.windows(4, 3))
+        .map(|w| Rect::new(w.x(), w.y(), w.width(), w.height())) {
+        assert_eq!(integral.sum(rect), Some(naive(&vv, rect)));
+    }
+    assert_eq!(integral.sum(Rect::new(4, 3, 0, 0)), Some(0));
+    assert_eq!(integral.sum(Rect::new(3, 0, 2, 1)), None);
+    assert_eq!(integral.sum(Rect::new(0, 0, 4, 4)), None);
+    assert_eq!(integral.sum(Rect::new(usize::MAX, 0, 1, 1)), None);
+    assert_eq!(integral.sum(Rect::new(0, 1, 1, usize::MAX)), None);
+
+    *vv.get_mut(1, 1).unwrap() = 100;
+    integral.update(1, 1, 5, 100);
+    assert_eq!(integral, vv.integral::<i64>());
+    integral.add(3, 2, -11);
+    *vv.get_mut(3, 2).unwrap() -= 11;
+    assert_eq!(integral, vv.integral::<i64>());
+    assert_eq!(integral.sum(Rect::new(0, 0, 4, 3)), Some(naive(&vv, Rect::new(0, 0, 4, 3))));
+
+    let slice = vv.slice(1, 1, 3, 2).unwrap();
+    let integral = slice.integral::<i64>();
+    assert_eq!(integral.width(), 3);
+    assert_eq!(integral.sum(Rect::new(0, 0, 1, 1)), Some(100));
+    assert_eq!(integral.sum(Rect::new(0, 0, 3, 2)), Some(naive(&vv, Rect::new(1, 1, 3, 2))));
+
+    assert!(Rect::new(1, 1, 2, 2).contains(2, 2));
+    assert!(!Rect::new(1, 1, 2, 2).contains(3, 1));
+    assert!(!Rect::new(1, 1, 0, 0).contains(1, 1));
+
+    let mut unsigned = VecVec::new(2, 2, 1u32);
+    *unsigned.get_mut(0, 0).unwrap() = 10;
+    let mut integral = unsigned.integral::<u32>();
+    assert_eq!(integral.sum(Rect::new(1, 1, 1, 1)), Some(1));
+    assert_eq!(integral.sum(Rect::new(0, 1, 2, 1)), Some(2));
+    integral.update(0, 0, 10, 3);
+    *unsigned.get_mut(0, 0).unwrap() = 3;
+    assert_eq!(integral, unsigned.integral::<u32>());
+
+    let bytes = VecVec::new(16, 16, 255u8);
+    let integral = bytes.integral::<u64>();
+    assert_eq!(integral.sum(Rect::new(0, 0, 16, 16)), Some(16 * 16 * 255));
+    assert_eq!(integral.sum(Rect::new(4, 4, 2, 3)), Some(6 * 255));
+    let floats = VecVec::new(2, 2, 0.5f32).integral::<f64>();
+    assert_eq!(floats.sum(Rect::new(0, 0, 2, 2)), Some(2.0));
+}
+
+#[test]
+fn ops() {
+    let a = VecVec::from_fn(3, 2, |x, y| (y * 3 + x) as i32);
+    let b = VecVec::new(3, 2, 2);
+
+    assert_eq!((&a + &b).as_slice(), s![s![2, 3, 4], s![5, 6, 7]]);
+    assert_eq!((&a - &b).as_slice(), s![s![-2, -1, 0], s![1, 2, 3]]);
+    assert_eq!((&a * &b).as_slice(), s![s![0, 2, 4], s![6, 8, 10]]);
+    assert_eq!((&a / &b).as_slice(), s![s![0, 0, 1], s![1, 2, 2]]);
+    assert_eq!((-&a).as_slice(), s![s![0, -1, -2], s![-3, -4, -5]]);
+    assert_eq!(-a.clone(), -&a);
+    assert_eq!((&a * 2).as_slice(), s![s![0, 2, 4], s![6, 8, 10]]);
+    assert_eq!(a.clone() + 1, &a + &VecVec::new(3, 2, 1));
+    assert_eq!(a.clone() - &b, &a - &b);
+
+    let (top, bottom) = a.hsplit_at(1).unwrap();
+    assert_eq!((&top + &bottom).as_slice(), s![s![3, 5, 7]]);
+    assert_eq!((&top * 10).as_slice(), s![s![0, 10, 20]]);
+    assert_eq!((-&bottom).as_slice(), s![s![-3, -4, -5]]);
+    assert_eq!(&top + &VecVec::new(3, 1, 1), VecVec::from_fn(3, 1, |x, _| x as i32 + 1));
+    assert_eq!(top.try_add(&bottom.slice(0, 0, 2, 1).unwrap()), None);
+    assert_eq!(a.try_sub(&top), None);
+    assert_eq!(a.try_mul(&a.as_slice()), Some(&a * &a));
+    assert_eq!(a.try_div(&b.as_slice()), Some(&a / &b));
+
+    let mut grid = a.clone();
+    grid += &b;
+    grid -= &a.as_slice();
+    assert_eq!(grid, b);
+    grid *= 3;
+    grid /= &b;
+    assert_eq!(grid, VecVec::new(3, 2, 3));
+
+    {
+        let mut slice = grid.slice_mut(1, 0, 2, 2).unwrap();
+        slice += &a.slice(0, 0, 2, 2).unwrap();
+        slice *= 2;
+        slice -= &VecVec::new(2, 2, 6);
+        slice /= 1;
+    }
+    assert_eq!(grid.as_slice(), s![s![3, 0, 2], s![3, 6, 8]]);
+
+    let floats = VecVec::new(2, 2, 1.5f64);
+    assert_eq!((&floats * 2.0).as_slice(), s![s![3.0, 3.0], s![3.0, 3.0]]);
+}
+
+#[test]
+#[should_panic(expected = "grid dimensions do not match")]
+fn ops_mismatch() {
+    let _ = &VecVec::new(2, 2, 0) + &VecVec::new(2, 3, 0);
+}
+
+#[test]
+fn matrix() {
+    let a = VecVec::from_fn(3, 2, |x, y| (y * 3 + x + 1) as i64);
+    assert_eq!(a.as_slice(), s![s![1, 2, 3], s![4, 5, 6]]);
+
+    assert_eq!(a.transpose().as_slice(), s![s![1, 4], s![2, 5], s![3, 6]]);
+    assert_eq!(a.slice(1, 0, 2, 2).unwrap().transpose().as_slice(), s![s![2, 5], s![3, 6]]);
+
+    let product = a.matmul(&a.transpose().as_slice()).unwrap();
+    assert_eq!(product.as_slice(), s![s![14, 32], s![32, 77]]);
+    assert_eq!(a.matmul(&a.as_slice()), None);
+    assert_eq!(a.matmul(&VecVec::identity(3).as_slice()), Some(a.clone()));
+    assert_eq!(VecVec::<i64>::identity(2).as_slice(), s![s![1, 0], s![0, 1]]);
+
+    assert_eq!(a.row(1).unwrap(), s![s![4, 5, 6]]);
+    assert_eq!(a.column(2).unwrap(), s![s![3], s![6]]);
+    assert_eq!(a.row(2), None);
+    assert_eq!(a.column(3), None);
+    let b = a.transpose();
+    assert_eq!(a.row(1).unwrap().dot(&b.column(1).unwrap()), Some(77));
+    assert_eq!(a.row(0).unwrap().dot(&a.row(1).unwrap()), Some(32));
+    assert_eq!(a.row(0).unwrap().dot(&a.column(0).unwrap()), None);
+    assert_eq!(a.dot(&a.as_slice()), None);
+
+    assert_eq!(product.trace(), Some(91));
+    assert_eq!(a.trace(), None);
+
+    assert_eq!(product.determinant(), Some(14 * 77 - 32 * 32));
+    assert_eq!(a.determinant(), None);
+    assert_eq!(VecVec::<i64>::identity(0).determinant(), Some(1));
+    let m = VecVec::from_fn(3, 3, |x, y| [[0, 2, 1], [1, 0, 3], [2, 1, 1]][y][x]);
+    assert_eq!(m.determinant(), Some(11));
+    let singular = VecVec::from_fn(3, 3, |x, y| (x + y) as i64);
+    assert_eq!(singular.determinant(), Some(0));
+
+    let m = VecVec::from_fn(3, 3, |x, y| [[0., 2., 1.], [1., 0., 3.], [2., 1., 1.]][y][x]);
+    assert!((m.determinant().unwrap() - 11.0f64).abs() < 1e-9);
+    let inverse = m.inverse().unwrap();
+    let identity = m.matmul(&inverse.as_slice()).unwrap();
+    for y in 0..3 {
+        for x in 0..3 {
+            let expected = if x == y { 1.0 } else { 0.0 };
+            assert!((identity.get(x, y).unwrap() - expected).abs() < 1e-9);
+        }
+    }
+    assert_eq!(VecVec::from_fn(2, 2, |x, _| x as f64).inverse(), None);
+    assert_eq!(VecVec::new(2, 1, 1.0f64).inverse(), None);
+}
+
+#[test]
+fn reduce() {
+    let vv = VecVec::from_fn(4, 3, |x, y| ((x * 7 + y * 5) % 11) as i32);
+    assert_eq!(vv.as_slice(), s![s![0, 7, 3, 10], s![5, 1, 8, 4], s![10, 6, 2, 9]]);
+
+    assert_eq!(vv.sum(), 65);
+    assert_eq!(vv.slice(1, 1, 2, 1).unwrap().product(), 8);
+    assert_eq!(vv.slice(0, 0, 0, 0).unwrap().sum(), 0);
+    assert_eq!(vv.slice(0, 0, 0, 0).unwrap().product(), 1);
+    assert_eq!(vv.min(), Some(&0));
+    assert_eq!(vv.max(), Some(&10));
+    assert_eq!(vv.argmin(), Some((0, 0)));
+    assert_eq!(vv.argmax(), Some((3, 0)));
+    assert_eq!(vv.slice(0, 0, 0, 3).unwrap().max(), None);
+    assert_eq!(vv.slice(0, 0, 0, 3).unwrap().argmin(), None);
+
+    assert_eq!(vv.row_sums(), [20, 18, 27]);
+    assert_eq!(vv.column_sums(), [15, 14, 13, 23]);
+    assert_eq!(vv.row_min(), [0, 1, 2]);
+    assert_eq!(vv.row_max(), [10, 8, 10]);
+    assert_eq!(vv.column_min(), [0, 1, 2, 4]);
+    assert_eq!(vv.column_max(), [10, 7, 8, 10]);
+    assert!(vv.slice(0, 0, 0, 3).unwrap().row_max().is_empty());
+
+    let mut vv = vv;
+    {
+        let slice = vv.slice_mut(1, 1, 3, 2).unwrap();
+        assert_eq!(slice.sum(), 30);
+        assert_eq!(slice.min(), Some(&1));
+        assert_eq!(slice.argmin(), Some((0, 0)));
+        assert_eq!(slice.argmax(), Some((2, 1)));
+        assert_eq!(slice.row_sums(), [13, 17]);
+        assert_eq!(slice.column_max(), [6, 8, 9]);
+    }
+
+    let floats = VecVec::from_fn(3, 1, |x, _| [1.0, f64::NAN, -1.0][x]);
+    assert_eq!(floats.min(), Some(&-1.0));
+    assert_eq!(floats.argmax(), Some((0, 0)));
+    let floats = VecVec::from_fn(3, 2, |x, y| [[f64::NAN, 2.0, 1.0], [f64::NAN; 3]][y][x]);
+    assert_eq!(floats.row(0).unwrap().min(), Some(&1.0));
+    assert_eq!(floats.argmax(), Some((1, 0)));
+    assert_eq!(floats.column_min()[1], 2.0);
+    assert!(floats.row_max()[1].is_nan());
+}
+
+#[test]
+fn search() {
+    let vv = VecVec::from_fn(4, 3, |x, y| ((x * 7 + y * 5) % 11) as i32);
+    assert_eq!(vv.as_slice(), s![s![0, 7, 3, 10], s![5, 1, 8, 4], s![10, 6, 2, 9]]);
+
+    assert_eq!(vv.position(|&v| v > 7), Some((3, 0)));
+    assert_eq!(vv.rposition(|&v| v > 7), Some((3, 2)));
+    assert_eq!(vv.position(|&v| v > 10), None);
+    assert_eq!(vv.rposition(|&v| v > 10), None);
+    assert_eq!(vv.positions(|&v| v % 2 == 0).collect::<Vec<_>>(),
+               [(0, 0), (3, 0), (2, 1), (3, 1), (0, 2), (1, 2), (2, 2)]);
+    assert!(vv.contains(&9));
+    assert!(!vv.contains(&11));
+    assert_eq!(vv.count(|&v| v >= 5), 7);
+
+    let slice = vv.slice(1, 1, 3, 2).unwrap();
+    assert_eq!(slice.position(|&v| v == 10), None);
+    assert_eq!(slice.position(|&v| v == 6), Some((0, 1)));
+    assert_eq!(slice.rposition(|&v| v < 5), Some((1, 1)));
+    assert_eq!(slice.positions(|&v| v > 5).collect::<Vec<_>>(), [(1, 0), (0, 1), (2, 1)]);
+    assert!(!slice.contains(&0));
+    assert_eq!(slice.count(|_| true), 6);
+
+    let needle = VecVec::from_fn(2, 2, |x, y| [[1, 8], [6, 2]][y][x]);
+    assert_eq!(vv.find_subgrid(&needle.as_slice()), Some((1, 1)));
+    assert_eq!(slice.find_subgrid(&needle.as_slice()), Some((0, 0)));
+    assert_eq!(slice.find_subgrid(&vv.slice(3, 1, 1, 2).unwrap()), Some((2, 0)));
+    assert_eq!(vv.find_subgrid(&VecVec::new(2, 2, 0).as_slice()), None);
+    assert_eq!(vv.find_subgrid(&VecVec::new(5, 1, 0).as_slice()), None);
+    assert_eq!(vv.find_subgrid(&VecVec::new(0, 1, 0).as_slice()), Some((0, 0)));
+}
+
+#[test]
+fn flood_fill() {
+    use vecvec::{Component, Connectivity, Rect};
+
+    let grid = |rows: &[&str]| {
+        VecVec::from_fn(rows[0].len(), rows.len(), |x, y| rows[y].as_bytes()[x] as char)
+    };
+    let mut vv = grid(&["..#..", ".#.#.", "#...#", "..#.."]);
+
+    let mut four = vv.clone();
+    assert_eq!(four.flood_fill(2, 2, 'o', Connectivity::Four), 8);
+    assert_eq!(four, grid(&["..#..", ".#o#.", "#ooo#", "oo#oo"]));
+    assert_eq!(four.flood_fill(9, 9, 'o', Connectivity::Four), 0);
+    assert_eq!(four.flood_fill(2, 2, 'o', Connectivity::Four), 8);
+
+    let mut eight = vv.clone();
+    assert_eq!(eight.flood_fill(2, 0, 'o', Connectivity::Eight), 5);
+    assert_eq!(eight, grid(&["..o..", ".o.o.", "o...o", "..#.."]));
+
+    {
+        let mut slice = vv.slice_mut(0, 0, 2, 4).unwrap();
+        assert_eq!(slice.flood_fill(0, 0, 'o', Connectivity::Four), 3);
+        assert_eq!(slice.flood_fill(1, 3, 'x', Connectivity::Eight), 3);
+    }
+    assert_eq!(vv, grid(&["oo#..", "o#.#.", "#x..#", "xx#.."]));
+
+    let vv = grid(&["..#..", ".#.#.", "#...#", "..#.."]);
+    let (labels, components) = vv.label_components(Connectivity::Four, |a, b| a == b);
+    assert_eq!(labels.as_slice(),
+               s![s![0, 0, 1, 2, 2], s![0, 3, 4, 5, 2], s![6, 4, 4, 4, 7], s![4, 4, 8, 4, 4]]);
+    assert_eq!(components.len(), 9);
+    assert_eq!(components[0], Component { area: 3, bounds: Rect::new(0, 0, 2, 2) });
+    assert_eq!(components[4], Component { area: 8, bounds: Rect::new(0, 1, 5, 3) });
+    assert_eq!(components[8], Component { area: 1, bounds: Rect::new(2, 3, 1, 1) });
+
+    let (labels, components) = vv.label_components(Connectivity::Eight, |a, b| a == b);
+    assert_eq!(components.len(), 3);
+    assert_eq!(labels.as_slice(),
+               s![s![0, 0, 1, 0, 0], s![0, 1, 0, 1, 0], s![1, 0, 0, 0, 1], s![0, 0, 2, 0, 0]]);
+    assert_eq!(components[0].area, 14);
+    assert_eq!(components[1], Component { area: 5, bounds: Rect::new(0, 0, 5, 3) });
+
+    let slice = vv.slice(1, 2, 3, 2).unwrap();
+    let (labels, components) = slice.label_components(Connectivity::Four, |a, b| a == b);
+    assert_eq!(labels.as_slice(), s![s![0, 0, 0], s![0, 1, 0]]);
+    assert_eq!(components[0].bounds, Rect::new(0, 0, 3, 2));
+}
+
+#[test]
+fn path() {
+    use vecvec::path;
+    use vecvec::Connectivity;
+
+    let rows = ["..#....", ".##.##.", "...#...", ".#...#."];
+    let map = VecVec::from_fn(7, 4, |x, y| rows[y].as_bytes()[x]);
+    let map = map.as_slice();
+    let open = |&c: &u8| c == b'.';
+    let cost = |&c: &u8| if c == b'.' { Some(1) } else { None };
+
+    let route = path::bfs(&map, (0, 0), (6, 0), Connectivity::Four, open).unwrap();
+    assert_eq!(route.len(), 13);
+    assert_eq!(route[0], (0, 0));
+    assert_eq!(route[12], (6, 0));
+    for pair in route.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        assert_eq!(a.0.max(b.0) - a.0.min(b.0) + a.1.max(b.1) - a.1.min(b.1), 1);
+        assert_eq!(map.get(b.0, b.1), Some(&b'.'));
+    }
+    assert_eq!(path::dijkstra(&map, (0, 0), (6, 0), Connectivity::Four, cost).unwrap().len(),
+               13);
+    assert_eq!(path::astar(&map, (0, 0), (6, 0), Connectivity::Four, cost).unwrap().len(),
+               13);
+
+    let route = path::bfs(&map, (0, 0), (6, 0), Connectivity::Eight, open).unwrap();
+    assert_eq!(route.len(), 8);
+    assert_eq!(path::astar(&map, (0, 0), (6, 0), Connectivity::Eight, cost).unwrap().len(),
+               8);
+
+    assert_eq!(path::bfs(&map, (1, 1), (1, 1), Connectivity::Four, open),
+               Some(vec![(1, 1)]));
+    assert_eq!(path::bfs(&map, (0, 0), (2, 0), Connectivity::Four, open), None);
+    assert_eq!(path::dijkstra(&map, (0, 0), (9, 9), Connectivity::Four, cost), None);
+    assert_eq!(path::astar(&map, (9, 9), (0, 0), Connectivity::Four, cost), None);
+
+    let distances = path::bfs_distances(&map, (0, 0), Connectivity::Four, open);
+    assert_eq!(distances.get(0, 3), Some(&Some(3)));
+    assert_eq!(distances.get(6, 0), Some(&Some(12)));
+    assert_eq!(distances.get(2, 0), Some(&None));
+
+    // Walking through the wall at (2, 0) costs 3, which beats going around.
+    let weighted = |&c: &u8| Some(if c == b'.' { 1 } else { 3 });
+    let route = path::dijkstra(&map, (0, 0), (6, 0), Connectivity::Four, weighted).unwrap();
+    assert_eq!(route, [(0, 0), (1, 0), (2, 0), (3, 0), (4, 0), (5, 0), (6, 0)]);
+    assert_eq!(path::astar(&map, (0, 0), (6, 0), Connectivity::Four, weighted).unwrap(), route);
+    let distances = path::dijkstra_distances(&map, (0, 0), Connectivity::Four, weighted);
+    assert_eq!(distances.get(6, 0), Some(&Some(8)));
+    assert_eq!(distances.get(1, 1), Some(&Some(4)));
+
+    let region = map.slice(3, 0, 4, 4).unwrap();
+    let route = path::bfs(&region, (0, 0), (3, 0), Connectivity::Four, open).unwrap();
+    assert_eq!(route, [(0, 0), (1, 0), (2, 0), (3, 0)]);
+    assert_eq!(path::bfs(&region, (0, 0), (0, 3), Connectivity::Four, open).unwrap().len(), 10);
+}
+
+#[test]
+fn distance() {
+    use vecvec::distance;
+
+    let features = VecVec::from_fn(9, 7, |x, y| (x * 5 + y * 3) % 13 == 0);
+    let points = (0..7)
+        .flat_map(|y| (0..9).map(move |x| (x, y)))
+        .filter(|&(x, y)| *features.get(x, y).unwrap())
+        .collect::<Vec<_>>();
+    let brute = |x: usize, y: usize, metric: &dyn Fn(f64, f64) -> f64| {
+        points.iter()
+            .map(|&(px, py)| metric((px as f64 - x as f64).abs(), (py as f64 - y as f64).abs()))
+            .fold(f64::INFINITY, f64::min)
+    };
+
+    let grid = features.as_slice();
+    let euclidean = distance::euclidean(&grid, |&b| b);
+    let manhattan = distance::manhattan(&grid, |&b| b);
+    let chebyshev = distance::chebyshev(&grid, |&b| b);
+    for y in 0..7 {
+        for x in 0..9 {
+            let expected = brute(x, y, &|dx, dy| (dx * dx + dy * dy).sqrt());
+            assert!((*euclidean.get(x, y).unwrap() as f64 - expected).abs() < 1e-5);
+            assert_eq!(*manhattan.get(x, y).unwrap() as f64, brute(x, y, &|dx, dy| dx + dy));
+            assert_eq!(*chebyshev.get(x, y).unwrap() as f64, brute(x, y, &f64::max));
+        }
+    }
+
+    let line = VecVec::from_fn(5, 1, |x, _| x == 1);
+    assert_eq!(distance::manhattan(&line.as_slice(), |&b| b).as_slice(), s![s![1, 0, 1, 2, 3]]);
+    assert_eq!(distance::euclidean(&line.as_slice(), |&b| b).as_slice(),
+               s![s![1.0, 0.0, 1.0, 2.0, 3.0]]);
+
+    let region = features.slice(1, 1, 3, 2).unwrap();
+    assert_eq!(distance::chebyshev(&region, |&b| b).as_slice(), s![s![1, 0, 1], s![1, 1, 1]]);
+
+    let empty = VecVec::new(3, 2, false);
+    assert_eq!(*distance::manhattan(&empty.as_slice(), |&b| b).get(1, 1).unwrap(), u32::MAX);
+    assert_eq!(*distance::euclidean(&empty.as_slice(), |&b| b).get(2, 0).unwrap(), f32::INFINITY);
+    assert_eq!(distance::euclidean(&empty.slice(0, 0, 0, 0).unwrap(), |&b| b).width(), 0);
+}
+
+#[test]
+fn draw() {
+    use vecvec::draw;
+
+    let render = |vv: &VecVec<char>| {
+        (0..vv.height())
+            .map(|y| (0..vv.width()).map(|x| *vv.get(x, y).unwrap()).collect::<String>())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(draw::line_points((0, 0), (4, 2)).collect::<Vec<_>>(),
+               [(0, 0), (1, 1), (2, 1), (3, 2), (4, 2)]);
+    assert_eq!(draw::line_points((2, 3), (2, 0)).collect::<Vec<_>>(),
+               [(2, 3), (2, 2), (2, 1), (2, 0)]);
+    assert_eq!(draw::line_points((1, 1), (1, 1)).collect::<Vec<_>>(), [(1, 1)]);
+    assert_eq!(draw::rect_points(0, 0, 3, 3).count(), 8);
+    assert_eq!(draw::rect_points(0, 0, 1, 3).count(), 3);
+    assert_eq!(draw::rect_points(0, 0, 0, 3).count(), 0);
+    assert_eq!(draw::fill_rect_points(-1, -1, 2, 3).count(), 6);
+    assert_eq!(draw::circle_points((0, 0), 0).collect::<Vec<_>>(), [(0, 0)]);
+    assert_eq!(draw::circle_points((0, 0), 1).collect::<Vec<_>>(),
+               [(0, -1), (-1, 0), (1, 0), (0, 1)]);
+
+    let vv = VecVec::new(7, 5, '.');
+    draw::line(&mut vv.as_mut_slice(), (-2, -1), (8, 4), '#');
+    assert_eq!(render(&vv), ["#......", ".##....", "...##..", ".....##", "......."]);
+
+    let vv = VecVec::new(7, 5, '.');
+    draw::rect(&mut vv.as_mut_slice(), 1, 1, 4, 3, '#');
+    draw::fill_rect(&mut vv.as_mut_slice(), 4, 3, 9, 9, 'o');
+    assert_eq!(render(&vv), [".......", ".####..", ".#..#..", ".###ooo", "....ooo"]);
+
+    let vv = VecVec::new(7, 7, '.');
+    draw::circle(&mut vv.as_mut_slice(), (3, 3), 3, '#');
+    assert_eq!(render(&vv),
+               ["..###..", ".#...#.", "#.....#", "#.....#", "#.....#", ".#...#.", "..###.."]);
+    draw::fill_circle(&mut vv.as_mut_slice(), (3, 3), 3, 'o');
+    assert_eq!(render(&vv),
+               ["..ooo..", ".ooooo.", "ooooooo", "ooooooo", "ooooooo", ".ooooo.", "..ooo.."]);
+
+    let mut vv = VecVec::new(7, 5, '.');
+    {
+        let mut slice = vv.slice_mut(1, 1, 5, 3).unwrap();
+        draw::polygon(&mut slice, &[(0, 0), (4, 0), (2, 4)], '#');
+        draw::fill_circle(&mut slice, (0, 2), 1, 'o');
+    }
+    assert_eq!(render(&vv), [".......", ".#####.", ".o..#..", ".oo.#..", "......."]);
+    assert_eq!(draw::polygon_points(&[(3, 3)]).collect::<Vec<_>>(), [(3, 3)]);
+    assert_eq!(draw::polygon_points(&[]).count(), 0);
+
+    let vv = VecVec::new(4, 4, 0);
+    draw::fill_rect(&mut vv.as_mut_slice(), 0, 0, 20000, 20000, 1);
+    draw::rect(&mut vv.as_mut_slice(), -1, 1, 20000, 20000, 2);
+    draw::fill_circle(&mut vv.as_mut_slice(), (3, 20002), 20000, 3);
+    assert_eq!(vv.as_slice(), s![s![1, 1, 1, 1], s![2, 2, 2, 2], s![3, 3, 3, 3], s![3, 3, 3, 3]]);
+    assert_eq!(draw::fill_rect_points(0, 0, 1 << 40, 1 << 40).nth(5), Some((5, 0)));
+    assert_eq!(draw::fill_circle_points((0, 0), 2).count(), 21);
+
+    for radius in 0..60 {
+        let (mut x, mut y, mut error) = (radius as isize, 0, 1 - radius as isize);
+        let mut outline = Vec::new();
+        while x >= y {
+            outline.extend_from_slice(&[(x, y), (y, x), (-y, x), (-x, y), (-x, -y), (-y, -x),
+                                        (y, -x), (x, -y)]);
+            y += 1;
+            if error < 0 {
+                error += 2 * y + 1;
+            } else {
+                x -= 1;
+                error += 2 * (y - x) + 1;
+            }
+        }
+        outline.sort_by_key(|&(x, y)| (y, x));
+        outline.dedup();
+        assert_eq!(draw::circle_points((0, 0), radius).collect::<Vec<_>>(), outline);
+        let filled = outline.iter()
+            .enumerate()
+            .filter(|&(i, &(_, y))| i == 0 || outline[i - 1].1 != y)
+            .flat_map(|(_, &(x, y))| (x..-x + 1).map(move |x| (x, y)))
+            .collect::<Vec<_>>();
+        assert_eq!(draw::fill_circle_points((0, 0), radius).collect::<Vec<_>>(), filled);
+    }
+
+    for &(from, to) in &[((-3, -9), (7, 12)), ((9, 2), (-4, 3)), ((5, -2), (5, 9)),
+                         ((-20, 13), (30, -11)), ((2, 2), (2, 2))] {
+        let (clipped, plotted) = (VecVec::new(6, 5, 0), VecVec::new(6, 5, 0));
+        draw::line(&mut clipped.as_mut_slice(), from, to, 1);
+        draw::plot(&mut plotted.as_mut_slice(), draw::line_points(from, to), 1);
+        assert_eq!(clipped, plotted);
+    }
+
+    let vv = VecVec::new(3, 3, '.');
+    draw::circle(&mut vv.as_mut_slice(), (1, 20_000_001), 20_000_000, '#');
+    assert_eq!(render(&vv), ["...", "###", "..."]);
+    draw::fill_circle(&mut vv.as_mut_slice(), (1, 20_000_001), 20_000_000, 'o');
+    assert_eq!(render(&vv), ["...", "ooo", "ooo"]);
+    draw::circle(&mut vv.as_mut_slice(), (0, 0), usize::MAX, '#');
+    assert_eq!(render(&vv), ["...", "ooo", "ooo"]);
+    draw::line(&mut vv.as_mut_slice(), (isize::MIN, isize::MIN), (isize::MAX, isize::MAX), '#');
+    assert_eq!(render(&vv), ["#..", "o#o", "oo#"]);
+    draw::polygon(&mut vv.as_mut_slice(), &[(-1 << 50, 0), (1 << 50, 0), (0, 1 << 50)], '+');
+    assert_eq!(render(&vv), ["+++", "o#o", "oo#"]);
+
+    let mut extreme = draw::line_points((isize::MIN, 0), (isize::MAX, 1));
+    assert_eq!(extreme.next(), Some((isize::MIN, 0)));
+    assert_eq!(draw::fill_rect_points(isize::MAX - 1, 0, 5, 1).count(), 2);
+    assert_eq!(draw::rect_points(isize::MAX, isize::MAX, 3, 3).count(), 1);
+    assert_eq!(draw::fill_circle_points((isize::MAX, 0), 1).count(), 4);
+    assert_eq!(draw::circle_points((0, isize::MIN), 1).count(), 3);
+}
+
+#[test]
+fn resample() {
+    let vv = VecVec::from_fn(4, 2, |x, y| (y * 4 + x) as u8 * 10);
+    assert_eq!(vv.as_slice(), s![s![0, 10, 20, 30], s![40, 50, 60, 70]]);
+
+    assert_eq!(vv.resize_nearest(2, 1).unwrap().as_slice(), s![s![50, 70]]);
+    assert_eq!(vv.resize_nearest(8, 2).unwrap().as_slice(),
+               s![s![0, 0, 10, 10, 20, 20, 30, 30], s![40, 40, 50, 50, 60, 60, 70, 70]]);
+    assert_eq!(vv.resize_nearest(4, 2).unwrap(), vv);
+    assert_eq!(vv.resize_nearest(0, 3).unwrap().height(), 3);
+
+    assert_eq!(vv.resize_bilinear(4, 2).unwrap(), vv);
+    assert_eq!(vv.resize_bilinear(2, 1).unwrap().as_slice(), s![s![25, 45]]);
+    assert_eq!(vv.resize_bilinear(8, 1).unwrap().as_slice(),
+               s![s![20, 23, 28, 33, 38, 43, 48, 50]]);
+
+    let floats = VecVec::from_fn(2, 2, |x, y| (x + 2 * y) as f64);
+    let up = floats.resize_bilinear(4, 4).unwrap();
+    assert_eq!(up.row(0).unwrap(), s![s![0.0, 0.25, 0.75, 1.0]]);
+    assert_eq!(up.column(0).unwrap(), s![s![0.0], s![0.5], s![1.5], s![2.0]]);
+
+    assert_eq!(vv.downsample_area(2, 2).unwrap().as_slice(), s![s![25, 45]]);
+    assert_eq!(vv.downsample_area(3, 1).unwrap().as_slice(), s![s![10], s![50]]);
+    assert_eq!(vv.downsample_area(1, 1).unwrap(), vv);
+    assert_eq!(vv.downsample_area(5, 1).unwrap().width(), 0);
+    assert_eq!(vv.downsample_area(0, 1), None);
+
+    let rgb = VecVec::from_fn(2, 1, |x, _| if x == 0 { [0u8, 100, 255] } else { [255, 100, 0] });
+    assert_eq!(rgb.downsample_area(2, 1).unwrap().as_slice(), s![s![[128, 100, 128]]]);
+    assert_eq!(rgb.resize_bilinear(1, 1).unwrap().as_slice(), s![s![[128, 100, 128]]]);
+
+    let slice = vv.slice(1, 0, 2, 2).unwrap();
+    assert_eq!(slice.resize_nearest(1, 1).unwrap().as_slice(), s![s![60]]);
+    assert_eq!(slice.downsample_area(2, 2).unwrap().as_slice(), s![s![35]]);
+    let empty = vv.slice(0, 0, 0, 0).unwrap();
+    assert_eq!(empty.resize_nearest(1, 1), None);
+    assert_eq!(empty.resize_bilinear(1, 1), None);
+    assert_eq!(empty.resize_bilinear(0, 0).unwrap().width(), 0);
+}
+
+#[test]
+fn stats() {
+    let vv = VecVec::from_fn(4, 2, |x, y| [[3, 1, 4, 1], [5, 9, 2, 6]][y][x]);
+
+    assert_eq!(vv.mean(), Some(31.0 / 8.0));
+    let values = [3., 1., 4., 1., 5., 9., 2., 6.];
+    let variance = values.iter().map(|v| (v - 3.875f64).powi(2)).sum::<f64>() / 8.0;
+    assert!((vv.variance().unwrap() - variance).abs() < 1e-12);
+    assert_eq!(vv.median(), Some(3.5));
+    assert_eq!(vv.percentile(0.0), Some(1.0));
+    assert_eq!(vv.percentile(100.0), Some(9.0));
+    assert_eq!(vv.percentile(25.0), Some(1.75));
+    assert_eq!(vv.percentile(101.0), None);
+    assert_eq!(vv.percentile(-1.0), None);
+
+    assert_eq!(vv.histogram(4, (1.0, 9.0)), [3, 2, 2, 1]);
+    assert_eq!(vv.histogram(2, (2.0, 5.0)), [2, 2]);
+    assert_eq!(vv.histogram(1, (4.0, 4.0)), [1]);
+    assert!(vv.histogram(0, (0.0, 9.0)).is_empty());
+    assert_eq!(vv.histogram(2, (9.0, 0.0)), [0, 0]);
+
+    let counts = vv.value_counts();
+    assert_eq!(counts.len(), 7);
+    assert_eq!(counts[&1], 2);
+    assert_eq!(counts[&9], 1);
+
+    let slice = vv.slice(1, 0, 2, 2).unwrap();
+    assert_eq!(slice.mean(), Some(4.0));
+    assert_eq!(slice.median(), Some(3.0));
+    assert_eq!(slice.variance(), Some(9.5));
+    assert_eq!(slice.value_counts().len(), 4);
+
+    let empty = vv.slice(0, 0, 0, 2).unwrap();
+    assert_eq!(empty.mean(), None);
+    assert_eq!(empty.variance(), None);
+    assert_eq!(empty.median(), None);
+    assert!(empty.value_counts().is_empty());
+
+    let floats = VecVec::from_fn(3, 1, |x, _| x as f32 * 0.5);
+    assert_eq!(floats.median(), Some(0.5));
+    assert_eq!(floats.histogram(2, (0.0, 1.0)), [1, 2]);
+    let floats = VecVec::from_fn(3, 1, |x, _| [f64::NAN, 0.9, 0.1][x]);
+    assert_eq!(floats.histogram(2, (0.0, 1.0)), [1, 1]);
+    assert_eq!(floats.percentile(0.0), Some(0.1));
+    assert_eq!(floats.percentile(100.0), Some(0.9));
+    assert_eq!(floats.median(), Some(0.5));
+    let floats = VecVec::from_fn(5, 1, |x, _| [f32::NAN, 4.0, f32::NAN, 1.0, 2.0][x]);
+    assert_eq!(floats.median(), Some(2.0));
+    assert!((floats.percentile(90.0).unwrap() - 3.6).abs() < 1e-12);
+    assert_eq!(VecVec::new(2, 2, f64::NAN).median(), None);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde() {
+    extern crate serde;
+    extern crate serde_json;
+
+    #[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+    struct Map {
+        #[serde(with = "vecvec::serde_rows")]
+        tiles: VecVec<u8>,
+    }
+
+    let vv = VecVec::from_fn(3, 2, |x, y| (y * 3 + x) as u8);
+    let json = serde_json::to_string(&vv).unwrap();
+    assert_eq!(json, r#"{"width":3,"height":2,"data":[0,1,2,3,4,5]}"#);
+    assert_eq!(serde_json::from_str::<VecVec<u8>>(&json).unwrap(), vv);
+
+    let slice = vv.slice(1, 0, 2, 2).unwrap();
+    let json = serde_json::to_string(&slice).unwrap();
+    assert_eq!(json, r#"{"width":2,"height":2,"data":[1,2,4,5]}"#);
+    assert!(serde_json::from_str::<VecVec<u8>>(&json).unwrap().as_slice() == slice);
+    let mut clone = vv.clone();
+    assert_eq!(serde_json::to_string(&clone.slice_mut(0, 1, 1, 1).unwrap()).unwrap(),
+               r#"{"width":1,"height":1,"data":[3]}"#);
+
+    let error = serde_json::from_str::<VecVec<u8>>(r#"{"width":3,"height":2,"data":[0,1]}"#)
+        .unwrap_err();
+    assert!(error.to_string().contains("expected 6 elements for a 3x2 VecVec, found 2"));
+    assert!(serde_json::from_str::<VecVec<u8>>(r#"{"width":3,"data":[]}"#).is_err());
+
+    let map = Map { tiles: vv.clone() };
+    let json = serde_json::to_string(&map).unwrap();
+    assert_eq!(json, r#"{"tiles":[[0,1,2],[3,4,5]]}"#);
+    assert_eq!(serde_json::from_str::<Map>(&json).unwrap(), map);
+    let empty = Map { tiles: VecVec::new(0, 2, 0) };
+    let json = serde_json::to_string(&empty).unwrap();
+    assert_eq!(json, r#"{"tiles":[[],[]]}"#);
+    assert_eq!(serde_json::from_str::<Map>(&json).unwrap(), empty);
+    let error = serde_json::from_str::<Map>(r#"{"tiles":[[0,1],[2]]}"#).unwrap_err();
+    assert!(error.to_string().contains("expected row 1 to have 2 elements like the first row, found 1"));
+}
+
+#[test]
+fn csv() {
+    use vecvec::csv::{Error, Reader, Writer};
+
+    let strings = |rows: &[&[&str]]| {
+        VecVec::from_fn(rows[0].len(), rows.len(), |x, y| rows[y][x].to_string())
+    };
+
+    let table = Reader::new().read("a,b,c\n1,\"x, \"\"y\"\"\",3\r\n").unwrap();
+    assert_eq!(table.header, None);
+    assert_eq!(table.cells, strings(s![s!["a", "b", "c"], s!["1", "x, \"y\"", "3"]]));
+
+    let table = Reader::new().has_header(true).read_parsed::<i32>("x,y\n1,2\n3,4").unwrap();
+    assert_eq!(table.header, Some(vec!["x".to_string(), "y".to_string()]));
+    assert_eq!(table.cells.as_slice(), s![s![1, 2], s![3, 4]]);
+
+    let table = Reader::tsv().read_parsed::<f64>("1.5\t2\n\"3\"\t-4\n").unwrap();
+    assert_eq!(table.cells.as_slice(), s![s![1.5, 2.0], s![3.0, -4.0]]);
+
+    let table = Reader::new().delimiter(';').read("a;\"multi\nline\"\n").unwrap();
+    assert_eq!(table.cells, strings(s![s!["a", "multi\nline"]]));
+
+    assert_eq!(Reader::new().read("1,2\n3\n").unwrap_err(),
+               Error::Ragged { row: 1, expected: 2, found: 1 });
+    let table = Reader::new().pad_with("0").read_parsed::<u8>("1\n2,3,4\n5,6\n").unwrap();
+    assert_eq!(table.cells.as_slice(), s![s![1, 0, 0], s![2, 3, 4], s![5, 6, 0]]);
+    let error = Reader::new().has_header(true).read_parsed::<u8>("h\n1\nx\n").unwrap_err();
+    assert_eq!(error, Error::Parse { row: 1, column: 0, field: "x".to_string() });
+    assert_eq!(error.to_string(), "invalid field \"x\" at row 1, column 0");
+    assert_eq!(Reader::new().read("\"open").unwrap_err(), Error::UnterminatedQuote);
+
+    let empty = Reader::new().has_header(true).read("").unwrap();
+    assert_eq!(empty.header, None);
+    assert_eq!((empty.cells.width(), empty.cells.height()), (0, 0));
+
+    let vv = VecVec::from_fn(3, 2, |x, y| (y * 3 + x) as i32);
+    assert_eq!(Writer::new().write(&vv.as_slice()), "0,1,2\n3,4,5\n");
+    assert_eq!(Writer::tsv().header(&["a", "b"]).write(&vv.slice(1, 0, 2, 2).unwrap()),
+               "a\tb\n1\t2\n4\t5\n");
+
+    let text = VecVec::from_fn(2, 1, |x, _| ["plain", "has \"quotes\", commas\nand lines"][x]);
+    let written = Writer::new().write(&text.as_slice());
+    assert_eq!(written, "plain,\"has \"\"quotes\"\", commas\nand lines\"\n");
+    assert_eq!(Reader::new().read(&written).unwrap().cells,
+               strings(s![s!["plain", "has \"quotes\", commas\nand lines"]]));
+
+    let mut out = Vec::new();
+    Writer::new().delimiter('|').write_to(&vv.row(1).unwrap(), &mut out).unwrap();
+    assert_eq!(out, b"3|4|5\n");
+}
+
+#[test]
+fn netpbm() {
+    use vecvec::netpbm::{self, Encoding};
+
+    let write = |vv: &VecVec<u8>, encoding| {
+        let mut bytes = Vec::new();
+        netpbm::write(&vv.as_slice(), &mut bytes, encoding).unwrap();
+        bytes
+    };
+
+    let gray = VecVec::from_fn(3, 2, |x, y| (y * 100 + x * 20) as u8);
+    assert_eq!(write(&gray, Encoding::Ascii), b"P2\n3 2\n255\n0 20 40\n100 120 140\n");
+    assert_eq!(write(&gray, Encoding::Binary), b"P5\n3 2\n255\n\x00\x14\x28\x64\x78\x8c");
+    for &encoding in &[Encoding::Ascii, Encoding::Binary] {
+        assert_eq!(netpbm::read::<u8, _>(&write(&gray, encoding)[..]).unwrap(), gray);
+    }
+
+    let mut pam = Vec::new();
+    netpbm::write_pam(&gray.slice(1, 1, 2, 1).unwrap(), &mut pam).unwrap();
+    assert_eq!(pam,
+               &b"P7\nWIDTH 2\nHEIGHT 1\nDEPTH 1\nMAXVAL 255\nTUPLTYPE GRAYSCALE\nENDHDR\n\x78\x8c"[..]);
+    assert_eq!(netpbm::read::<u8, _>(&pam[..]).unwrap().as_slice(), s![s![120, 140]]);
+
+    let bits = VecVec::from_fn(10, 2, |x, y| (x + y) % 3 == 0);
+    let mut pbm = Vec::new();
+    netpbm::write(&bits.as_slice(), &mut pbm, Encoding::Binary).unwrap();
+    assert_eq!(pbm, b"P4\n10 2\n\x92\x40\x24\x80");
+    assert_eq!(netpbm::read::<bool, _>(&pbm[..]).unwrap(), bits);
+    let ascii = "P1\n# comment\n3 2\n010\n1 1 0\n";
+    assert_eq!(netpbm::read::<bool, _>(ascii.as_bytes()).unwrap().as_slice(),
+               s![s![false, true, false], s![true, true, false]]);
+
+    let mut pam = Vec::new();
+    netpbm::write_pam(&netpbm::read::<bool, _>(&pbm[..]).unwrap().as_slice(), &mut pam).unwrap();
+    assert!(pam.ends_with(b"TUPLTYPE BLACKANDWHITE\nENDHDR\n\x00\x01\x01\x00\x01\x01\x00\x01\x01\x00\
+                            \x01\x01\x00\x01\x01\x00\x01\x01\x00\x01"));
+    let mut round_trip = Vec::new();
+    let bitmap = netpbm::read::<bool, _>(&pam[..]).unwrap();
+    netpbm::write(&bitmap.as_slice(), &mut round_trip, Encoding::Binary).unwrap();
+    assert_eq!(round_trip, pbm);
+
+    let deep = VecVec::from_fn(2, 1, |x, _| x as u16 * 1000 + 1);
+    let mut pgm = Vec::new();
+    netpbm::write(&deep.as_slice(), &mut pgm, Encoding::Binary).unwrap();
+    assert_eq!(pgm, b"P5\n2 1\n65535\n\x00\x01\x03\xe9");
+    assert_eq!(netpbm::read::<u16, _>(&pgm[..]).unwrap(), deep);
+
+    let rgb = VecVec::from_fn(2, 1, |x, _| [x as u8, 128, 255]);
+    let mut ppm = Vec::new();
+    netpbm::write(&rgb.as_slice(), &mut ppm, Encoding::Ascii).unwrap();
+    assert_eq!(ppm, b"P3\n2 1\n255\n0 128 255 1 128 255\n");
+    assert_eq!(netpbm::read::<[u8; 3], _>(&ppm[..]).unwrap(), rgb);
+    assert_eq!(netpbm::read::<[u8; 3], _>(&b"P3 1 1 15 15 0 7"[..]).unwrap().as_slice(),
+               s![s![[255, 0, 119]]]);
+
+    let rgba = VecVec::new(1, 1, [1u8, 2, 3, 4]);
+    assert!(netpbm::write(&rgba.as_slice(), Vec::new(), Encoding::Binary).is_err());
+    let mut pam = Vec::new();
+    netpbm::write_pam(&rgba.as_slice(), &mut pam).unwrap();
+    assert_eq!(netpbm::read::<[u8; 4], _>(&pam[..]).unwrap(), rgba);
+
+    assert!(netpbm::read::<u8, _>(&ppm[..]).is_err());
+    assert!(netpbm::read::<bool, _>(&b"P2 1 1 255 0"[..]).is_err());
+    assert!(netpbm::read::<u8, _>(&b"P5 2 2 255 \x00"[..]).is_err());
+    assert!(netpbm::read::<u8, _>(&b"P2 1 1 10 11"[..]).is_err());
+    assert!(netpbm::read::<u8, _>(&b"P2 1 1 255 65537"[..]).is_err());
+    assert!(netpbm::read::<bool, _>(&b"P2 1 1 65537 1"[..]).is_err());
+    assert!(netpbm::read::<u8, _>(&b"P2 1 1 0 0"[..]).is_err());
+    let pam = b"P7\nWIDTH 1\nHEIGHT 1\nDEPTH 1\nMAXVAL 65791\nENDHDR\n\x00\x01";
+    assert!(netpbm::read::<u8, _>(&pam[..]).is_err());
+    assert!(netpbm::read::<u8, _>(&b"P9 1 1"[..]).is_err());
+}
+
+#[test]
+fn text() {
+    let vv = VecVec::parse_text("#..#\r\n.##.\n").unwrap();
+    assert_eq!(vv.as_slice(), s![s!['#', '.', '.', '#'], s!['.', '#', '#', '.']]);
+    assert_eq!(vv.text().separator("").to_string(), "#..#\n.##.");
+    assert_eq!(VecVec::parse_text("#.\n#"), None);
+    let empty = VecVec::parse_text("").unwrap();
+    assert_eq!((empty.width(), empty.height()), (0, 0));
+
+    let walls = VecVec::parse_text_with("#.\n.#", |c| c == '#').unwrap();
+    assert_eq!(walls.as_slice(), s![s![true, false], s![false, true]]);
+
+    let digits = VecVec::parse_text_with("123\n456", |c| c.to_digit(10).unwrap()).unwrap();
+    assert_eq!(digits.to_string(), "1 2 3\n4 5 6");
+    assert_eq!(format!("{:2}", digits.slice(1, 0, 2, 2).unwrap()), " 2  3\n 5  6");
+    assert_eq!(digits.text().separator(",").row_separator(";").to_string(), "1,2,3;4,5,6");
+
+    let floats = VecVec::from_fn(2, 1, |x, _| x as f64 / 3.0);
+    assert_eq!(format!("{:.2}", floats), "0.00 0.33");
+}
+
+#[test]
+fn table() {
+    let vv = VecVec::from_fn(3, 2, |x, y| (y * 3 + x) as i32 * 5 - 3);
+    assert_eq!(format!("{:#}", vv), "   0  1  2\n0 -3  2  7\n1 12 17 22");
+    assert_eq!(format!("{:#?}", vv.slice(1, 0, 2, 2).unwrap()), "   0  1\n0  2  7\n1 17 22");
+    assert_eq!(format!("{:#4}", vv.row(1).unwrap()), "     0    1    2\n0   12   17   22");
+    assert_eq!(format!("{:?}", vv), "[[-3, 2, 7], [12, 17, 22]]");
+
+    let floats = VecVec::from_fn(2, 1, |x, _| x as f64 / 3.0);
+    assert_eq!(format!("{:#.2?}", floats), "     0    1\n0 0.00 0.33");
+    let strings = VecVec::new(1, 1, "a");
+    assert_eq!(format!("{:#?}", strings), "    0\n0 \"a\"");
+
+    let large = VecVec::from_fn(40, 30, |x, y| y * 40 + x);
+    assert_eq!(format!("{:#}", large),
+               "       0    1    2 ...   37   38   39\n\
+                \x20 0    0    1    2 ...   37   38   39\n\
+                \x20 1   40   41   42 ...   77   78   79\n\
+                \x20 2   80   81   82 ...  117  118  119\n\
+                ...\n\
+                \x2027 1080 1081 1082 ... 1117 1118 1119\n\
+                \x2028 1120 1121 1122 ... 1157 1158 1159\n\
+                \x2029 1160 1161 1162 ... 1197 1198 1199");
+}
+
+#[test]
+fn npy() {
+    let npy = |version: u8, header: &str, data: &[u8]| {
+        let mut header = header.to_string();
+        header.push('\n');
+        let mut bytes = b"\x93NUMPY".to_vec();
+        bytes.extend_from_slice(&[version, 0]);
+        if version == 1 {
+            bytes.extend_from_slice(&(header.len() as u16).to_le_bytes());
+        } else {
+            bytes.extend_from_slice(&(header.len() as u32).to_le_bytes());
+        }
+        bytes.extend_from_slice(header.as_bytes());
+        bytes.extend_from_slice(data);
+        bytes
+    };
+
+    let vv = VecVec::from_fn(3, 2, |x, y| (y * 3 + x) as i32 - 2);
+    let mut bytes = Vec::new();
+    vv.write_npy(&mut bytes).unwrap();
+    assert_eq!(bytes.len(), 128 + 6 * 4);
+    assert_eq!(&bytes[..10], b"\x93NUMPY\x01\x00\x76\x00");
+    let header = b"{'descr': '<i4', 'fortran_order': False, 'shape': (2, 3), }";
+    assert!(bytes[10..128].starts_with(header));
+    assert_eq!(bytes[127], b'\n');
+    assert_eq!(VecVec::<i32>::read_npy(&bytes[..]).unwrap(), vv);
+
+    let mut bytes = Vec::new();
+    vv.slice(1, 0, 2, 2).unwrap().write_npy(&mut bytes).unwrap();
+    assert_eq!(VecVec::<i32>::read_npy(&bytes[..]).unwrap().as_slice(), s![s![-1, 0], s![2, 3]]);
+
+    let header = "{'descr': '>u2', 'fortran_order': True, 'shape': (2, 3), }";
+    let fortran = npy(2, header, &[0, 1, 0, 4, 0, 2, 0, 5, 0, 3, 0, 6]);
+    assert_eq!(VecVec::<u16>::read_npy(&fortran[..]).unwrap().as_slice(),
+               s![s![1, 2, 3], s![4, 5, 6]]);
+
+    let header = "{'descr': '|b1', 'fortran_order': False, 'shape': (1, 2), }";
+    let bools = npy(1, header, &[1, 0]);
+    assert_eq!(VecVec::<bool>::read_npy(&bools[..]).unwrap().as_slice(), s![s![true, false]]);
+
+    let floats = VecVec::from_fn(2, 2, |x, y| x as f64 - y as f64 * 0.5);
+    let mut bytes = Vec::new();
+    floats.write_npy(&mut bytes).unwrap();
+    assert_eq!(VecVec::<f64>::read_npy(&bytes[..]).unwrap(), floats);
+
+    let error = VecVec::<f32>::read_npy(&bytes[..]).unwrap_err();
+    assert_eq!(error.to_string(), "expected dtype \"f4\", found \"<f8\"");
+    let header = "{'descr': '<i4', 'fortran_order': False, 'shape': (6,), }";
+    let error = VecVec::<i32>::read_npy(&npy(1, header, &[0; 24])[..]).unwrap_err();
+    assert_eq!(error.to_string(), "expected a 2-dimensional array, found shape [6]");
+    let header = "{'descr': '<i4', 'fortran_order': False, 'shape': (2, 3), }";
+    assert!(VecVec::<i32>::read_npy(&npy(1, header, &[0; 20])[..]).is_err());
+    assert!(VecVec::<i32>::read_npy(&b"PK\x03\x04"[..]).is_err());
+}
+
+#[test]
+fn binary() {
+    use vecvec::binary::Encoding;
+
+    let vv = VecVec::from_fn(3, 2, |x, y| if x == 2 && y == 1 { 7u8 } else { 5 });
+    let header = |encoding: u8| {
+        let mut bytes = b"VVEC\x01".to_vec();
+        bytes.push(encoding);
+        bytes.extend_from_slice(&[3, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0]);
+        bytes
+    };
+
+    let mut raw = Vec::new();
+    vv.write_to(&mut raw, Encoding::Raw).unwrap();
+    assert_eq!(raw, [&header(0)[..], &[5, 5, 5, 5, 5, 7]].concat());
+    assert_eq!(VecVec::<u8>::read_from(&raw[..]).unwrap(), vv);
+
+    let mut rle = Vec::new();
+    vv.write_to(&mut rle, Encoding::RunLength).unwrap();
+    let runs = [3, 0, 0, 0, 5, 2, 0, 0, 0, 0, 1, 0, 0, 0, 2];
+    assert_eq!(rle, [&header(1)[..], &runs].concat());
+    assert_eq!(VecVec::<u8>::read_from(&rle[..]).unwrap(), vv);
+
+    let tiles = VecVec::from_fn(50, 40, |x, y| [(x / 10) as u16, (y / 20) as u16 * 1000]);
+    for &encoding in &[Encoding::Raw, Encoding::RunLength] {
+        let mut bytes = Vec::new();
+        tiles.write_to(&mut bytes, encoding).unwrap();
+        assert_eq!(VecVec::<[u16; 2]>::read_from(&bytes[..]).unwrap(), tiles);
+    }
+    let mut bytes = Vec::new();
+    tiles.write_to(&mut bytes, Encoding::RunLength).unwrap();
+    assert!(bytes.len() < 1000);
+
+    let floats = VecVec::from_fn(2, 2, |x, y| x as f32 - y as f32 / 4.0);
+    let mut bytes = Vec::new();
+    floats.slice(1, 0, 1, 2).unwrap().write_to(&mut bytes, Encoding::RunLength).unwrap();
+    assert_eq!(VecVec::<f32>::read_from(&bytes[..]).unwrap().as_slice(), s![s![1.0], s![0.75]]);
+
+    assert!(VecVec::<u16>::read_from(&raw[..]).is_err());
+    assert!(VecVec::<u8>::read_from(&raw[..raw.len() - 1]).is_err());
+    assert!(VecVec::<u8>::read_from(&rle[..rle.len() - 5]).is_err());
+    let mut long_run = header(1);
+    long_run.extend_from_slice(&[7, 0, 0, 0, 1]);
+    assert!(VecVec::<u8>::read_from(&long_run[..]).is_err());
+    let mut huge = b"VVEC\x01\x00".to_vec();
+    huge.extend_from_slice(&[0xff; 16]);
+    huge.extend_from_slice(&[1, 0, 0, 0]);
+    assert!(VecVec::<u8>::read_from(&huge[..]).is_err());
+    let mut truncated = b"VVEC\x01\x00".to_vec();
+    truncated.extend_from_slice(&[0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 1, 0, 0, 0]);
+    assert!(VecVec::<u8>::read_from(&truncated[..]).is_err());
+}
+
+#[cfg(feature = "ndarray")]
+#[test]
+fn ndarray() {
+    use ndarray::{array, Array2, ArrayView2, ArrayViewMut2};
+
+    let vv = VecVec::from_fn(4, 3, |x, y| y * 4 + x);
+    let view = ArrayView2::from(vv.slice(1, 1, 2, 2).unwrap());
+    assert_eq!(view, array![[5, 6], [9, 10]]);
+    assert_eq!(view.strides(), &[4, 1]);
+    assert_eq!(view.sum(), 30);
+    assert_eq!(ArrayView2::from(vv.slice(4, 3, 0, 0).unwrap()).len(), 0);
+
+    let mut vv = vv;
+    {
+        let (mut top, mut bottom) = vv.hsplit_at_mut(1).unwrap();
+        let mut view = ArrayViewMut2::from(bottom.slice_mut(2, 0, 2, 2).unwrap());
+        view += 100;
+        *top.get_mut(0, 0).unwrap() = 7;
+    }
+    assert_eq!(vv.as_slice(), s![s![7, 1, 2, 3], s![4, 5, 106, 107], s![8, 9, 110, 111]]);
+
+    let array = Array2::from(vv.clone());
+    assert_eq!(array.dim(), (3, 4));
+    assert_eq!(array[[1, 2]], 106);
+    assert_eq!(VecVec::from(array), vv);
+
+    let transposed = VecVec::from(array![[1, 2, 3], [4, 5, 6]].reversed_axes());
+    assert_eq!(transposed.as_slice(), s![s![1, 4], s![2, 5], s![3, 6]]);
+    let mut array = array![[1, 2, 3], [4, 5, 6], [7, 8, 9]];
+    array.slice_collapse(ndarray::s![1.., ..]);
+    assert_eq!(VecVec::from(array).as_slice(), s![s![4, 5, 6], s![7, 8, 9]]);
+}
+
+#[cfg(feature = "image")]
+#[test]
+fn image() {
+    use std::convert::TryFrom;
+
+    use image::{imageops, GenericImageView, GrayImage, Luma, Rgb, RgbImage};
+    use vecvec::ImageTooLarge;
+
+    let vv = VecVec::from_fn(4, 3, |x, y| Rgb([x as u8, y as u8, 9]));
+    let rgb = RgbImage::try_from(vv.clone()).unwrap();
+    assert_eq!(rgb.dimensions(), (4, 3));
+    assert_eq!(*rgb.get_pixel(3, 1), Rgb([3, 1, 9]));
+    assert_eq!(&rgb.as_raw()[..6], &[0, 0, 9, 1, 0, 9]);
+    assert_eq!(VecVec::from(rgb), vv);
+
+    let slice = vv.slice(1, 1, 2, 2).unwrap();
+    assert_eq!(GenericImageView::dimensions(&slice), (2, 2));
+    assert_eq!(slice.get_pixel(1, 0), Rgb([2, 1, 9]));
+    let mut copy = RgbImage::new(3, 2);
+    imageops::replace(&mut copy, &slice, 1, 0);
+    assert_eq!(&copy.as_raw()[3..9], &[1, 1, 9, 2, 1, 9]);
+    let flipped = imageops::flip_horizontal(&slice);
+    assert_eq!(VecVec::from(flipped).as_slice(),
+               s![s![Rgb([2, 1, 9]), Rgb([1, 1, 9])], s![Rgb([2, 2, 9]), Rgb([1, 2, 9])]]);
+
+    let gray = GrayImage::from_fn(2, 1, |x, _| Luma([x as u8 * 200]));
+    assert_eq!(VecVec::from(gray).as_slice(), s![s![Luma([0]), Luma([200])]]);
+
+    let wide = VecVec::new(1 << 32, 0, Luma([0u8]));
+    assert_eq!(GrayImage::try_from(wide),
+               Err(ImageTooLarge { width: 1 << 32, height: 0 }));
+    assert_eq!(GrayImage::try_from(VecVec::new(0, 1 << 32, Luma([0u8]))).unwrap_err().height,
+               1 << 32);
+}
+
+#[cfg(feature = "nalgebra")]
+#[test]
+fn nalgebra() {
+    use nalgebra::{dmatrix, DMatrix, DMatrixView, Dyn};
+
+    let vv = VecVec::from_fn(3, 2, |x, y| (y * 3 + x) as f64);
+    let matrix = DMatrix::from(vv.clone());
+    assert_eq!(matrix, dmatrix![0.0, 1.0, 2.0; 3.0, 4.0, 5.0]);
+    assert_eq!(matrix.as_slice(), &[0.0, 3.0, 1.0, 4.0, 2.0, 5.0]);
+    assert_eq!(VecVec::from(matrix.transpose()).as_slice(),
+               s![s![0.0, 3.0], s![1.0, 4.0], s![2.0, 5.0]]);
+    assert_eq!(VecVec::from(matrix), vv);
+
+    let vv = VecVec::from_fn(4, 4, |x, y| if x == y { 2.0 } else { (x + y) as f64 });
+    let view = DMatrixView::<f64, Dyn, Dyn>::from(vv.slice(2, 2, 2, 2).unwrap());
+    assert_eq!(view, dmatrix![2.0, 5.0; 5.0, 2.0]);
+    assert_eq!(view.strides(), (4, 1));
+    let solution = view.clone_owned().lu().solve(&dmatrix![7.0; 7.0]).unwrap();
+    assert!((solution - dmatrix![1.0; 1.0]).norm() < 1e-12);
+    assert_eq!(DMatrixView::<f64, Dyn, Dyn>::from(vv.slice(4, 4, 0, 0).unwrap()).len(), 0);
+}